@@ -0,0 +1,35 @@
+use pinocchio::program_error::ProgramError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EscrowError {
+    /// `make` was called with a zero seed, which every default client shares.
+    ZeroSeed = 1,
+    /// `take` was called after the escrow's expiry.
+    Expired = 2,
+    /// The escrow account was written in a layout version this program can't read.
+    VersionMismatch = 3,
+    /// A batch names more escrows than it can process within its compute budget.
+    TooManyItems = 4,
+}
+
+impl From<EscrowError> for ProgramError {
+    fn from(e: EscrowError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+/// Decodes a `ProgramError::Custom` code back into its `EscrowError` variant.
+#[cfg(feature = "client")]
+impl TryFrom<u32> for EscrowError {
+    type Error = ProgramError;
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(EscrowError::ZeroSeed),
+            2 => Ok(EscrowError::Expired),
+            3 => Ok(EscrowError::VersionMismatch),
+            4 => Ok(EscrowError::TooManyItems),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
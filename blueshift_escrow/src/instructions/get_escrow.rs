@@ -0,0 +1,61 @@
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError, ProgramResult,
+};
+
+use crate::state::Escrow;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct GetEscrowAccounts<'a> {
+    pub escrow: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetEscrowAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [escrow, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        ProgramAccount::check(escrow)?;
+
+        Ok(Self { escrow })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: writes the raw `Escrow` bytes (including the memo) into the
+/// transaction return data.
+pub struct GetEscrow<'a> {
+    pub accounts: GetEscrowAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetEscrow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetEscrowAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetEscrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = self.accounts.escrow.try_borrow_data()?;
+
+        // Make sure the account actually holds an escrow
+        Escrow::load(&data)?;
+
+        set_return_data(&data);
+
+        Ok(())
+    }
+}
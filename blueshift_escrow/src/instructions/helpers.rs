@@ -1,12 +1,18 @@
+use core::mem::size_of;
+
 use pinocchio::{
-    account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    account_info::{AccountInfo, Ref},
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{find_program_address, Pubkey},
     sysvars::{rent::Rent, Sysvar},
     ProgramResult,
 };
 use pinocchio_associated_token_account::instructions::{Create, CreateIdempotent};
+use pinocchio_token::state::TokenAccount;
+
+use crate::state::Escrow;
 
 // ─── SignerAccount ──────────────────────────────────────────────────────────
 
@@ -22,20 +28,164 @@ impl SignerAccount {
     }
 }
 
+// ─── SystemProgram ──────────────────────────────────────────────────────────
+
+pub struct SystemProgram;
+
+impl SystemProgram {
+    #[inline(always)]
+    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key() != &pinocchio_system::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+}
+
+// ─── TokenProgram ───────────────────────────────────────────────────────────
+
+// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = [
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+];
+
+pub struct TokenProgram;
+
+impl TokenProgram {
+    /// Accepts the legacy SPL Token program or Token-2022.
+    #[inline(always)]
+    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key() != &pinocchio_token::ID && account.key() != &TOKEN_2022_PROGRAM_ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+
+    /// The token program owning `account`, which CPIs on it must target.
+    #[inline(always)]
+    pub fn owner_of(account: &AccountInfo) -> Result<&'static Pubkey, ProgramError> {
+        if account.is_owned_by(&pinocchio_token::ID) {
+            Ok(&pinocchio_token::ID)
+        } else if account.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+            Ok(&TOKEN_2022_PROGRAM_ID)
+        } else {
+            Err(ProgramError::InvalidAccountOwner)
+        }
+    }
+}
+
 // ─── MintInterface ──────────────────────────────────────────────────────────
 
 pub struct MintInterface;
 
 impl MintInterface {
+    /// Accepts legacy mints and Token-2022 mints without extensions. Escrow
+    /// transfers are plain `Transfer`s, which Token-2022 refuses for mints
+    /// with a transfer fee, and a fee would leave the vault holding less than
+    /// the escrow records; an extension-free mint is exactly the base size.
     #[inline(always)]
     pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
+        TokenProgram::owner_of(account)?;
+        if account.data_len() != pinocchio_token::state::Mint::LEN {
+            return Err(ProgramError::InvalidAccountData);
         }
         Ok(())
     }
 }
 
+// ─── TokenInterface ─────────────────────────────────────────────────────────
+
+const TRANSFER: u8 = 3;
+const CLOSE_ACCOUNT: u8 = 9;
+
+/// Token account reads and CPIs that work for legacy and Token-2022 accounts
+/// alike, targeting whichever program owns the account.
+pub struct TokenInterface;
+
+impl TokenInterface {
+    /// Loads a legacy or Token-2022 token account. Both share the legacy
+    /// base layout, and Token-2022 extensions only ever follow it.
+    #[inline(always)]
+    pub fn account(account: &AccountInfo) -> Result<Ref<'_, TokenAccount>, ProgramError> {
+        TokenProgram::owner_of(account)?;
+        let data = account.try_borrow_data()?;
+        if data.len() < TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Ref::map(data, |data| unsafe {
+            TokenAccount::from_bytes(data)
+        }))
+    }
+
+    #[inline(always)]
+    pub fn transfer(
+        from: &AccountInfo,
+        to: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let mut data = [0u8; 9];
+        data[0] = TRANSFER;
+        data[1..].copy_from_slice(&amount.to_le_bytes());
+        invoke_signed(
+            &Instruction {
+                program_id: TokenProgram::owner_of(from)?,
+                accounts: &[
+                    AccountMeta::writable(from.key()),
+                    AccountMeta::writable(to.key()),
+                    AccountMeta::readonly_signer(authority.key()),
+                ],
+                data: &data,
+            },
+            &[from, to, authority],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    pub fn close_account(
+        account: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        invoke_signed(
+            &Instruction {
+                program_id: TokenProgram::owner_of(account)?,
+                accounts: &[
+                    AccountMeta::writable(account.key()),
+                    AccountMeta::writable(destination.key()),
+                    AccountMeta::readonly_signer(authority.key()),
+                ],
+                data: &[CLOSE_ACCOUNT],
+            },
+            &[account, destination, authority],
+            signers,
+        )
+    }
+}
+
+// ─── NativeMint ─────────────────────────────────────────────────────────────
+
+/// The wrapped SOL mint, So11111111111111111111111111111111111111112.
+pub const NATIVE_MINT: Pubkey = [
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84, 0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55, 0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+pub struct NativeMint;
+
+impl NativeMint {
+    /// Whether `mint` stands for plain SOL: the all-zero key or the wrapped
+    /// SOL mint.
+    #[inline(always)]
+    pub fn is_native(mint: &Pubkey) -> bool {
+        mint == &[0u8; 32] || mint == &NATIVE_MINT
+    }
+}
+
 // ─── AssociatedTokenAccount ─────────────────────────────────────────────────
 
 pub struct AssociatedTokenAccount;
@@ -48,7 +198,8 @@ impl AssociatedTokenAccount {
         mint: &AccountInfo,
         token_program: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        if !ata.is_owned_by(token_program.key()) {
+        // The mint decides which token program its accounts live under
+        if !mint.is_owned_by(token_program.key()) || !ata.is_owned_by(token_program.key()) {
             return Err(ProgramError::InvalidAccountOwner);
         }
         let (expected_key, _) = find_program_address(
@@ -140,12 +291,49 @@ impl ProgramAccount {
         Ok(())
     }
 
+    #[inline(always)]
+    pub fn realloc(account: &AccountInfo, new_len: usize, payer: &AccountInfo) -> ProgramResult {
+        Self::realloc_with_rent(account, new_len, payer, &Rent::get()?)
+    }
+
+    /// As `realloc`, against an already loaded `Rent`.
+    #[inline(always)]
+    pub fn realloc_with_rent(
+        account: &AccountInfo,
+        new_len: usize,
+        payer: &AccountInfo,
+        rent: &Rent,
+    ) -> ProgramResult {
+        if new_len < account.data_len() {
+            return Err(ProgramError::InvalidRealloc);
+        }
+
+        // Top up the account so it stays rent-exempt at its new size
+        let required = rent.minimum_balance(new_len);
+        let current = account.lamports();
+        if required > current {
+            pinocchio_system::instructions::Transfer {
+                from: payer,
+                to: account,
+                lamports: required - current,
+            }
+            .invoke()?;
+        }
+
+        account.realloc(new_len, true)?;
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn close(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
         let lamports = account.lamports();
         unsafe {
             *account.borrow_mut_lamports_unchecked() = 0;
-            *destination.borrow_mut_lamports_unchecked() += lamports;
+            let destination_lamports = destination.borrow_mut_lamports_unchecked();
+            *destination_lamports = destination_lamports
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
         }
 
         let mut data = account.try_borrow_mut_data()?;
@@ -161,3 +349,53 @@ impl ProgramAccount {
         Ok(())
     }
 }
+
+// ─── EscrowAccount ──────────────────────────────────────────────────────────
+
+pub struct EscrowAccount;
+
+impl EscrowAccount {
+    /// Loads `escrow` with `Escrow::from_account_info`, which checks it is
+    /// this program's and sits at the PDA its stored seed, maker and bump
+    /// derive, then checks `maker` is the maker it stores.
+    ///
+    /// # Safety
+    ///
+    /// The escrow data must not be mutably borrowed while the returned
+    /// reference is in use.
+    #[inline(always)]
+    pub unsafe fn check<'a>(
+        escrow: &'a AccountInfo,
+        maker: &AccountInfo,
+    ) -> Result<&'a Escrow, ProgramError> {
+        let state = Escrow::from_account_info(escrow)?;
+        if maker.key() != &state.maker {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(state)
+    }
+}
+
+// ─── Events ─────────────────────────────────────────────────────────────────
+
+/// Size of an `encode_escrow_event` record.
+pub const ESCROW_EVENT_LEN: usize = 3 * size_of::<u64>() + 3 * size_of::<Pubkey>();
+
+/// Fixed layout shared by the make, take and refund logs: the escrow's seed,
+/// maker, mint_a and mint_b, then the mint_a and mint_b amounts the
+/// instruction moved, integers little-endian.
+#[inline(always)]
+pub fn encode_escrow_event(
+    escrow: &Escrow,
+    amount_a: u64,
+    amount_b: u64,
+) -> [u8; ESCROW_EVENT_LEN] {
+    let mut event = [0u8; ESCROW_EVENT_LEN];
+    event[0..8].copy_from_slice(&escrow.seed.to_le_bytes());
+    event[8..40].copy_from_slice(&escrow.maker);
+    event[40..72].copy_from_slice(&escrow.mint_a);
+    event[72..104].copy_from_slice(&escrow.mint_b);
+    event[104..112].copy_from_slice(&amount_a.to_le_bytes());
+    event[112..120].copy_from_slice(&amount_b.to_le_bytes());
+    event
+}
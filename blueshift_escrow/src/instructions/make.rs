@@ -3,13 +3,13 @@ use core::mem::size_of;
 use pinocchio::{
     account_info::AccountInfo,
     instruction::Seed,
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{find_program_address, Pubkey},
     ProgramResult,
 };
-use pinocchio_token::instructions::Transfer;
 
-use crate::state::Escrow;
+use crate::{errors::EscrowError, state::Escrow};
 
 use super::helpers::*;
 
@@ -37,9 +37,14 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MakeAccounts<'a> {
         };
 
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgram::check(token_program)?;
         SignerAccount::check(maker)?;
         MintInterface::check(mint_a)?;
-        MintInterface::check(mint_b)?;
+        // A native SOL receive side needs no mint account
+        if !NativeMint::is_native(mint_b.key()) {
+            MintInterface::check(mint_b)?;
+        }
         AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
 
         Ok(Self {
@@ -61,15 +66,73 @@ pub struct MakeInstructionData {
     pub seed: u64,
     pub receive: u64,
     pub amount: u64,
+    pub memo: [u8; 32],
+    pub expiry: i64,
+    pub allowed_taker: Pubkey,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub end_price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
 }
 
 impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() * 3 {
+        const MAKE_DATA_LEN: usize = size_of::<u64>() * 3;
+        const MAKE_DATA_LEN_WITH_MEMO: usize = MAKE_DATA_LEN + size_of::<[u8; 32]>();
+        const MAKE_DATA_LEN_WITH_EXPIRY: usize = MAKE_DATA_LEN_WITH_MEMO + size_of::<i64>();
+        const MAKE_DATA_LEN_WITH_TAKER: usize = MAKE_DATA_LEN_WITH_EXPIRY + size_of::<Pubkey>();
+        const MAKE_DATA_LEN_WITH_FEE: usize =
+            MAKE_DATA_LEN_WITH_TAKER + size_of::<u16>() + size_of::<Pubkey>();
+        const MAKE_DATA_LEN_WITH_AUCTION: usize = MAKE_DATA_LEN_WITH_FEE + size_of::<u64>() * 3;
+
+        // The memo, expiry, allowed taker, fee and auction terms are
+        // optional, in that order; an omitted memo is stored zeroed, an
+        // omitted expiry never expires, an omitted taker lets anyone take, an
+        // omitted fee is zero and without auction terms the price is fixed
+        if ![
+            MAKE_DATA_LEN,
+            MAKE_DATA_LEN_WITH_MEMO,
+            MAKE_DATA_LEN_WITH_EXPIRY,
+            MAKE_DATA_LEN_WITH_TAKER,
+            MAKE_DATA_LEN_WITH_FEE,
+            MAKE_DATA_LEN_WITH_AUCTION,
+        ]
+        .contains(&data.len())
+        {
             return Err(ProgramError::InvalidInstructionData);
         }
+        let memo = match data.get(MAKE_DATA_LEN..MAKE_DATA_LEN_WITH_MEMO) {
+            Some(memo) => memo.try_into().unwrap(),
+            None => [0u8; 32],
+        };
+        let expiry = match data.get(MAKE_DATA_LEN_WITH_MEMO..MAKE_DATA_LEN_WITH_EXPIRY) {
+            Some(expiry) => i64::from_le_bytes(expiry.try_into().unwrap()),
+            None => 0,
+        };
+        let allowed_taker = match data.get(MAKE_DATA_LEN_WITH_EXPIRY..MAKE_DATA_LEN_WITH_TAKER) {
+            Some(allowed_taker) => allowed_taker.try_into().unwrap(),
+            None => Pubkey::default(),
+        };
+        let (fee_bps, fee_recipient) =
+            match data.get(MAKE_DATA_LEN_WITH_TAKER..MAKE_DATA_LEN_WITH_FEE) {
+                Some(fee) => (
+                    u16::from_le_bytes(fee[..2].try_into().unwrap()),
+                    fee[2..].try_into().unwrap(),
+                ),
+                None => (0, Pubkey::default()),
+            };
+        let (end_price, start_time, end_time) =
+            match data.get(MAKE_DATA_LEN_WITH_FEE..MAKE_DATA_LEN_WITH_AUCTION) {
+                Some(auction) => (
+                    u64::from_le_bytes(auction[0..8].try_into().unwrap()),
+                    i64::from_le_bytes(auction[8..16].try_into().unwrap()),
+                    i64::from_le_bytes(auction[16..24].try_into().unwrap()),
+                ),
+                None => (0, 0, 0),
+            };
 
         let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
         let receive = u64::from_le_bytes(data[8..16].try_into().unwrap());
@@ -79,11 +142,32 @@ impl<'a> TryFrom<&'a [u8]> for MakeInstructionData {
         if amount == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
+        // A zero seed is what a client that forgot to set one sends, and
+        // every such escrow for a maker would collide on the same PDA
+        if seed == 0 {
+            return Err(EscrowError::ZeroSeed.into());
+        }
+        // A fee needs somewhere to go and can't exceed what the maker receives
+        if fee_bps > 10_000 || (fee_bps != 0 && fee_recipient == Pubkey::default()) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // An auction needs a window to decay over and may only get cheaper
+        if end_time != 0 && (end_time <= start_time || end_price > receive) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
         Ok(Self {
             seed,
             receive,
             amount,
+            memo,
+            expiry,
+            allowed_taker,
+            fee_bps,
+            fee_recipient,
+            end_price,
+            start_time,
+            end_time,
         })
     }
 }
@@ -122,7 +206,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Make<'a> {
             Seed::from(&bump_binding),
         ];
 
-        ProgramAccount::init::<Escrow>(accounts.maker, accounts.escrow, &escrow_seeds, Escrow::LEN)?;
+        ProgramAccount::init::<Escrow>(
+            accounts.maker,
+            accounts.escrow,
+            &escrow_seeds,
+            Escrow::LEN,
+        )?;
 
         // Initialize the vault ATA
         AssociatedTokenAccount::init(
@@ -148,7 +237,7 @@ impl<'a> Make<'a> {
     pub fn process(&mut self) -> ProgramResult {
         // Populate the escrow account
         let mut data = self.accounts.escrow.try_borrow_mut_data()?;
-        let escrow = Escrow::load_mut(data.as_mut())?;
+        let escrow = Escrow::init(data.as_mut())?;
 
         escrow.set_inner(
             self.instruction_data.seed,
@@ -158,15 +247,38 @@ impl<'a> Make<'a> {
             self.instruction_data.receive,
             [self.bump],
         );
+        escrow.set_deposit(self.instruction_data.amount);
+        escrow.set_memo(self.instruction_data.memo);
+        escrow.set_expiry(self.instruction_data.expiry);
+        escrow.set_allowed_taker(self.instruction_data.allowed_taker);
+        escrow.set_fee(
+            self.instruction_data.fee_bps,
+            self.instruction_data.fee_recipient,
+        );
+        escrow.set_auction(
+            self.instruction_data.end_price,
+            self.instruction_data.start_time,
+            self.instruction_data.end_time,
+        );
+        escrow.set_native_b(NativeMint::is_native(self.accounts.mint_b.key()));
 
         // Transfer tokens to vault
-        Transfer {
-            from: self.accounts.maker_ata_a,
-            to: self.accounts.vault,
-            authority: self.accounts.maker,
-            amount: self.instruction_data.amount,
-        }
-        .invoke()?;
+        TokenInterface::transfer(
+            self.accounts.maker_ata_a,
+            self.accounts.vault,
+            self.accounts.maker,
+            self.instruction_data.amount,
+            &[],
+        )?;
+
+        sol_log_data(&[
+            b"make",
+            &encode_escrow_event(
+                escrow,
+                self.instruction_data.amount,
+                self.instruction_data.receive,
+            ),
+        ]);
 
         Ok(())
     }
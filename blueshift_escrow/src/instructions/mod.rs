@@ -1,9 +1,17 @@
+pub mod get_escrow;
 pub mod helpers;
 pub mod make;
+pub mod reclaim_excess;
 pub mod refund;
+pub mod refund_batch;
 pub mod take;
+pub mod update_receive;
 
+pub use get_escrow::*;
 pub use helpers::*;
 pub use make::*;
+pub use reclaim_excess::*;
 pub use refund::*;
+pub use refund_batch::*;
 pub use take::*;
+pub use update_receive::*;
@@ -0,0 +1,104 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct ReclaimExcessAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+    pub mint_a: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub maker_ata_a: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ReclaimExcessAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow, mint_a, vault, maker_ata_a, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        TokenProgram::check(token_program)?;
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+        MintInterface::check(mint_a)?;
+        AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
+        AssociatedTokenAccount::check(maker_ata_a, maker, mint_a, token_program)?;
+
+        Ok(Self {
+            maker,
+            escrow,
+            mint_a,
+            vault,
+            maker_ata_a,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Returns whatever the vault holds above the escrow's original deposit to
+/// the maker, leaving the escrow open for a taker.
+pub struct ReclaimExcess<'a> {
+    pub accounts: ReclaimExcessAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ReclaimExcess<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ReclaimExcessAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ReclaimExcess<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let escrow = unsafe { EscrowAccount::check(self.accounts.escrow, self.accounts.maker)? };
+
+        // Only the balance above the intended deposit is returned
+        let excess = {
+            let vault = TokenInterface::account(self.accounts.vault)?;
+            if vault.mint() != &escrow.mint_a {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            vault.amount().saturating_sub(escrow.deposit)
+        };
+        if excess == 0 {
+            return Ok(());
+        }
+
+        let seed_binding = escrow.seed.to_le_bytes();
+        let bump_binding = escrow.bump;
+        let escrow_seeds = [
+            Seed::from(b"escrow"),
+            Seed::from(self.accounts.maker.key().as_ref()),
+            Seed::from(&seed_binding),
+            Seed::from(&bump_binding),
+        ];
+        let signer = Signer::from(&escrow_seeds);
+
+        // Transfer the excess Token A from Vault back to Maker
+        TokenInterface::transfer(
+            self.accounts.vault,
+            self.accounts.maker_ata_a,
+            self.accounts.escrow,
+            excess,
+            &[signer],
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,14 +1,10 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::create_program_address,
     ProgramResult,
 };
-use pinocchio_token::{
-    instructions::{CloseAccount, Transfer},
-    state::TokenAccount,
-};
 
 use crate::state::Escrow;
 
@@ -37,6 +33,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
         };
 
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgram::check(token_program)?;
         SignerAccount::check(maker)?;
         ProgramAccount::check(escrow)?;
         MintInterface::check(mint_a)?;
@@ -56,6 +54,9 @@ impl<'a> TryFrom<&'a [AccountInfo]> for RefundAccounts<'a> {
 
 // ─── Instruction ────────────────────────────────────────────────────────────
 
+/// Returns whatever mint_a the vault still holds to the maker and closes the
+/// escrow. The amount is read from the vault rather than derived from the
+/// escrow terms, so a partially filled escrow refunds exactly its remainder.
 pub struct Refund<'a> {
     pub accounts: RefundAccounts<'a>,
 }
@@ -84,61 +85,62 @@ impl<'a> Refund<'a> {
     pub const DISCRIMINATOR: &'a u8 = &2;
 
     pub fn process(&mut self) -> ProgramResult {
-        let data = self.accounts.escrow.try_borrow_data()?;
-        let escrow = Escrow::load(&data)?;
-
-        // Verify the escrow PDA is valid
-        let escrow_key = create_program_address(
-            &[
-                b"escrow",
-                self.accounts.maker.key(),
-                &escrow.seed.to_le_bytes(),
-                &escrow.bump,
-            ],
-            &crate::ID,
-        )?;
-        if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        let escrow = unsafe { EscrowAccount::check(self.accounts.escrow, self.accounts.maker)? };
+
+        refund_escrow(
+            self.accounts.maker,
+            self.accounts.escrow,
+            escrow,
+            self.accounts.vault,
+            self.accounts.maker_ata_a,
+        )
+    }
+}
 
-        // Verify maker matches the escrow's maker
-        if self.accounts.maker.key() != &escrow.maker {
+/// Moves everything left in `vault` to `maker_ata_a`, then closes the vault
+/// and `escrow_account` into `maker`. The caller has already loaded `escrow`
+/// and checked `maker` is its maker.
+pub fn refund_escrow(
+    maker: &AccountInfo,
+    escrow_account: &AccountInfo,
+    escrow: &Escrow,
+    vault: &AccountInfo,
+    maker_ata_a: &AccountInfo,
+) -> ProgramResult {
+    let seed_binding = escrow.seed.to_le_bytes();
+    let bump_binding = escrow.bump;
+    let escrow_seeds = [
+        Seed::from(b"escrow"),
+        Seed::from(maker.key().as_ref()),
+        Seed::from(&seed_binding),
+        Seed::from(&bump_binding),
+    ];
+    let signer = Signer::from(&escrow_seeds);
+
+    // The vault must be the escrow's own token account of the escrowed mint,
+    // and its balance is the unfilled remainder to return
+    let amount = {
+        let vault = TokenInterface::account(vault)?;
+        if vault.mint() != &escrow.mint_a || vault.owner() != escrow_account.key() {
             return Err(ProgramError::InvalidAccountData);
         }
+        vault.amount()
+    };
 
-        let seed_binding = escrow.seed.to_le_bytes();
-        let bump_binding = escrow.bump;
-        let escrow_seeds = [
-            Seed::from(b"escrow"),
-            Seed::from(self.accounts.maker.key().as_ref()),
-            Seed::from(&seed_binding),
-            Seed::from(&bump_binding),
-        ];
-        let signer = Signer::from(&escrow_seeds);
-
-        let amount = TokenAccount::from_account_info(self.accounts.vault)?.amount();
-
-        // Transfer Token A from Vault back to Maker
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.maker_ata_a,
-            authority: self.accounts.escrow,
-            amount,
-        }
-        .invoke_signed(&[signer.clone()])?;
+    // Transfer Token A from Vault back to Maker
+    TokenInterface::transfer(
+        vault,
+        maker_ata_a,
+        escrow_account,
+        amount,
+        core::slice::from_ref(&signer),
+    )?;
 
-        // Close the Vault
-        CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
-        }
-        .invoke_signed(&[signer.clone()])?;
+    // Close the Vault
+    TokenInterface::close_account(vault, maker, escrow_account, &[signer])?;
 
-        // Close the Escrow account
-        drop(data);
-        ProgramAccount::close(self.accounts.escrow, self.accounts.maker)?;
+    sol_log_data(&[b"refund", &encode_escrow_event(escrow, amount, 0)]);
 
-        Ok(())
-    }
+    // Close the Escrow account
+    ProgramAccount::close(escrow_account, maker)
 }
@@ -0,0 +1,98 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{errors::EscrowError, state::Escrow};
+
+use super::{helpers::*, refund::refund_escrow};
+
+/// Most escrows a single batch may refund. Each costs two token CPIs and a
+/// PDA derivation, so this keeps a full batch well inside the default
+/// per-instruction compute budget.
+pub const MAX_REFUND_BATCH: usize = 8;
+
+/// Accounts each escrow in the batch takes: escrow, vault, maker_ata_a.
+const REFUND_ACCOUNTS_LEN: usize = 3;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct RefundBatchAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// `(escrow, vault, maker_ata_a)` triples, one per escrow to refund.
+    pub escrows: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RefundBatchAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, token_program, escrows @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        TokenProgram::check(token_program)?;
+        SignerAccount::check(maker)?;
+
+        if escrows.is_empty() || escrows.len() % REFUND_ACCOUNTS_LEN != 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        // Refuse up front rather than run out of compute mid-batch
+        if escrows.len() / REFUND_ACCOUNTS_LEN > MAX_REFUND_BATCH {
+            return Err(EscrowError::TooManyItems.into());
+        }
+
+        Ok(Self {
+            maker,
+            token_program,
+            escrows,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Refunds several of the maker's escrows at once, each exactly as `Refund`
+/// would. Escrows made by someone else are skipped rather than failing the
+/// batch, so a stale entry in a maker's list doesn't block the rest. Every
+/// `maker_ata_a` must already exist, since the batch carries no mints to
+/// create them from.
+pub struct RefundBatch<'a> {
+    pub accounts: RefundBatchAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RefundBatch<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RefundBatchAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RefundBatch<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        for triple in self.accounts.escrows.chunks_exact(REFUND_ACCOUNTS_LEN) {
+            let [escrow_account, vault, maker_ata_a] = triple else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let escrow = unsafe { Escrow::from_account_info(escrow_account)? };
+            if self.accounts.maker.key() != &escrow.maker {
+                continue;
+            }
+
+            refund_escrow(
+                self.accounts.maker,
+                escrow_account,
+                escrow,
+                vault,
+                maker_ata_a,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,14 @@
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::create_program_address,
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::{
-    instructions::{CloseAccount, Transfer},
-    state::TokenAccount,
-};
 
-use crate::state::Escrow;
+use crate::{errors::EscrowError, state::Escrow};
 
 use super::helpers::*;
 
@@ -28,26 +26,44 @@ pub struct TakeAccounts<'a> {
     pub maker_ata_b: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    /// Funds any ATAs take creates: the first trailing signer, or the taker
+    /// itself when none is supplied.
+    pub payer: &'a AccountInfo,
+    /// Trailing accounts, searched by address for the fee destination when
+    /// the escrow charges a fee.
+    pub remaining: &'a [AccountInfo],
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for TakeAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [taker, maker, escrow, mint_a, mint_b, vault, taker_ata_a, taker_ata_b, maker_ata_b, system_program, token_program, _] =
+        let [taker, maker, escrow, mint_a, mint_b, vault, taker_ata_a, taker_ata_b, maker_ata_b, system_program, token_program, _, remaining @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
         // Basic Accounts Checks
+        SystemProgram::check(system_program)?;
+        TokenProgram::check(token_program)?;
         SignerAccount::check(taker)?;
         ProgramAccount::check(escrow)?;
         MintInterface::check(mint_a)?;
-        MintInterface::check(mint_b)?;
-        AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+        // Native SOL is paid in lamports, with no mint or taker_ata_b involved
+        if !NativeMint::is_native(mint_b.key()) {
+            MintInterface::check(mint_b)?;
+            AssociatedTokenAccount::check(taker_ata_b, taker, mint_b, token_program)?;
+        }
         AssociatedTokenAccount::check(vault, escrow, mint_a, token_program)?;
 
+        // A taker PDA holding data can't fund account creation, so its
+        // program may pass a separate payer
+        let payer = remaining
+            .iter()
+            .find(|account| account.is_signer())
+            .unwrap_or(taker);
+
         Ok(Self {
             taker,
             maker,
@@ -60,42 +76,93 @@ impl<'a> TryFrom<&'a [AccountInfo]> for TakeAccounts<'a> {
             vault,
             system_program,
             token_program,
+            payer,
+            remaining,
         })
     }
 }
 
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+pub struct TakeInstructionData {
+    /// Amount of mint_b to fill, or zero (or no data at all) to fill
+    /// whatever is left of the escrow's `receive`.
+    pub fill_amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for TakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let fill_amount = match data.len() {
+            0 => 0,
+            8 => u64::from_le_bytes(data.try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { fill_amount })
+    }
+}
+
 // ─── Instruction ────────────────────────────────────────────────────────────
 
+/// The taker only has to be a signer and the authority of `taker_ata_b`, so a
+/// program can take on behalf of a PDA it controls by invoking `take` with
+/// `invoke_signed` over the PDA's seeds. Such a PDA usually can't pay rent,
+/// in which case the caller appends a funded signer as `payer`.
+///
+/// A partial fill pays the maker `fill_amount` of mint_b and the taker the
+/// same share of the remaining deposit, rounded down, then shrinks both
+/// terms and leaves the escrow open. The fill that brings `receive` to zero
+/// takes everything left in the vault and closes it, so earlier rounding
+/// only ever leaves mint_a for the last taker and the maker always gets the
+/// full `receive`.
+///
+/// An auction escrow is priced at `Escrow::current_receive` for the current
+/// clock and can only be filled whole.
+///
+/// Either mint may be a legacy SPL Token or an extension-free Token-2022
+/// mint, but both must live under the one `token_program` passed in.
+///
+/// When the receive side is native SOL the taker pays the maker in lamports
+/// instead, and `taker_ata_b`/`maker_ata_b` are passed but unused.
 pub struct Take<'a> {
     pub accounts: TakeAccounts<'a>,
+    pub instruction_data: TakeInstructionData,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Take<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Take<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = TakeAccounts::try_from(accounts)?;
+        let instruction_data = TakeInstructionData::try_from(data)?;
 
         // Initialize necessary accounts
         AssociatedTokenAccount::init_if_needed(
             accounts.taker_ata_a,
             accounts.mint_a,
-            accounts.taker,
+            accounts.payer,
             accounts.taker,
             accounts.system_program,
             accounts.token_program,
         )?;
 
-        AssociatedTokenAccount::init_if_needed(
-            accounts.maker_ata_b,
-            accounts.mint_b,
-            accounts.taker,
-            accounts.maker,
-            accounts.system_program,
-            accounts.token_program,
-        )?;
+        if !NativeMint::is_native(accounts.mint_b.key()) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.maker_ata_b,
+                accounts.mint_b,
+                accounts.payer,
+                accounts.maker,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
 
-        Ok(Self { accounts })
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
     }
 }
 
@@ -103,21 +170,32 @@ impl<'a> Take<'a> {
     pub const DISCRIMINATOR: &'a u8 = &1;
 
     pub fn process(&mut self) -> ProgramResult {
-        let data = self.accounts.escrow.try_borrow_data()?;
-        let escrow = Escrow::load(&data)?;
-
-        // Check if the escrow PDA is valid
-        let escrow_key = create_program_address(
-            &[
-                b"escrow",
-                self.accounts.maker.key(),
-                &escrow.seed.to_le_bytes(),
-                &escrow.bump,
-            ],
-            &crate::ID,
-        )?;
-        if &escrow_key != self.accounts.escrow.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+        let escrow = unsafe { EscrowAccount::check(self.accounts.escrow, self.accounts.maker)? };
+
+        // The receive side must be the escrow's own, which also decides how
+        // the maker is paid
+        if self.accounts.mint_b.key() != &escrow.mint_b {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // An OTC offer can only be taken by the taker it names
+        if let Some(allowed_taker) = escrow.allowed_taker() {
+            if allowed_taker != self.accounts.taker.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // A lapsed offer can only be refunded
+        let now = Clock::get()?.unix_timestamp;
+        if escrow.is_expired(now) {
+            return Err(EscrowError::Expired.into());
+        }
+
+        // An auction is taken whole at its current price, which moves too
+        // fast for a taker to size a partial fill against
+        let receive = escrow.current_receive(now);
+        if escrow.is_auction() && self.instruction_data.fill_amount != 0 {
+            return Err(ProgramError::InvalidInstructionData);
         }
 
         let seed_binding = escrow.seed.to_le_bytes();
@@ -130,38 +208,127 @@ impl<'a> Take<'a> {
         ];
         let signer = Signer::from(&escrow_seeds);
 
-        let amount = TokenAccount::from_account_info(self.accounts.vault)?.amount();
+        let fill = match self.instruction_data.fill_amount {
+            0 => receive,
+            fill_amount => fill_amount,
+        };
+        if fill > receive {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let closing = fill == receive;
+
+        // mint_a must leave from the escrow's own vault: a token account of
+        // the escrowed mint whose authority is the escrow PDA signing below
+        let amount = {
+            let vault = TokenInterface::account(self.accounts.vault)?;
+            if vault.mint() != &escrow.mint_a || vault.owner() != self.accounts.escrow.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            match closing {
+                true => vault.amount(),
+                false => (escrow.deposit as u128 * fill as u128 / receive as u128) as u64,
+            }
+        };
+        if !closing && amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
 
         // Transfer from the Vault to the Taker
-        Transfer {
-            from: self.accounts.vault,
-            to: self.accounts.taker_ata_a,
-            authority: self.accounts.escrow,
+        TokenInterface::transfer(
+            self.accounts.vault,
+            self.accounts.taker_ata_a,
+            self.accounts.escrow,
             amount,
-        }
-        .invoke_signed(&[signer.clone()])?;
+            core::slice::from_ref(&signer),
+        )?;
 
-        // Close the Vault
-        CloseAccount {
-            account: self.accounts.vault,
-            destination: self.accounts.maker,
-            authority: self.accounts.escrow,
+        // Close the Vault once nothing is left to fill
+        if closing {
+            TokenInterface::close_account(
+                self.accounts.vault,
+                self.accounts.maker,
+                self.accounts.escrow,
+                &[signer],
+            )?;
         }
-        .invoke_signed(&[signer.clone()])?;
-
-        // Transfer from the Taker to the Maker
-        Transfer {
-            from: self.accounts.taker_ata_b,
-            to: self.accounts.maker_ata_b,
-            authority: self.accounts.taker,
-            amount: escrow.receive,
+
+        // The taker pays the filled `receive` in full; the fee, rounded down
+        // so any dust stays with the maker, comes out of the maker's share
+        let fee = (fill as u128 * escrow.fee_bps as u128 / 10_000) as u64;
+        self.pay_b(
+            escrow,
+            self.accounts.maker,
+            self.accounts.maker_ata_b,
+            fill - fee,
+        )?;
+        if fee != 0 {
+            let fee_destination = self.fee_destination(escrow)?;
+            self.pay_b(escrow, fee_destination, fee_destination, fee)?;
         }
-        .invoke()?;
 
-        // Close the Escrow
-        drop(data);
-        ProgramAccount::close(self.accounts.escrow, self.accounts.taker)?;
+        sol_log_data(&[b"take", &encode_escrow_event(escrow, amount, fill)]);
+
+        let (receive, deposit) = (escrow.receive - fill, escrow.deposit.saturating_sub(amount));
+
+        // Close the Escrow, or shrink its terms to what is left
+        if closing {
+            return ProgramAccount::close(self.accounts.escrow, self.accounts.taker);
+        }
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+        escrow.set_receive(receive);
+        escrow.set_deposit(deposit);
 
         Ok(())
     }
+
+    /// Pays `amount` of mint_b from the taker: lamports to `wallet` for a
+    /// native SOL escrow, tokens to `token_account` otherwise.
+    fn pay_b(
+        &self,
+        escrow: &Escrow,
+        wallet: &AccountInfo,
+        token_account: &AccountInfo,
+        amount: u64,
+    ) -> ProgramResult {
+        match escrow.is_native_b != 0 {
+            true => pinocchio_system::instructions::Transfer {
+                from: self.accounts.taker,
+                to: wallet,
+                lamports: amount,
+            }
+            .invoke(),
+            false => TokenInterface::transfer(
+                self.accounts.taker_ata_b,
+                token_account,
+                self.accounts.taker,
+                amount,
+                &[],
+            ),
+        }
+    }
+
+    /// The trailing account the fee goes to: the fee recipient itself for a
+    /// native SOL escrow, otherwise its mint_b ATA.
+    fn fee_destination(&self, escrow: &Escrow) -> Result<&'a AccountInfo, ProgramError> {
+        let expected = match escrow.is_native_b != 0 {
+            true => escrow.fee_recipient,
+            false => {
+                find_program_address(
+                    &[
+                        &escrow.fee_recipient,
+                        self.accounts.token_program.key(),
+                        &escrow.mint_b,
+                    ],
+                    &pinocchio_associated_token_account::ID,
+                )
+                .0
+            }
+        };
+        self.accounts
+            .remaining
+            .iter()
+            .find(|account| account.key() == &expected)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
 }
@@ -0,0 +1,98 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::state::Escrow;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct UpdateReceiveAccounts<'a> {
+    pub maker: &'a AccountInfo,
+    pub escrow: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateReceiveAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [maker, escrow, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Basic Accounts Checks
+        SignerAccount::check(maker)?;
+        ProgramAccount::check(escrow)?;
+
+        Ok(Self { maker, escrow })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+pub struct UpdateReceiveInstructionData {
+    pub receive: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateReceiveInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let receive = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if receive == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { receive })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Reprices an open escrow in place: the maker sets a new `receive` for
+/// whatever deposit is still in the vault, keeping the same PDA and rent.
+pub struct UpdateReceive<'a> {
+    pub accounts: UpdateReceiveAccounts<'a>,
+    pub instruction_data: UpdateReceiveInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateReceive<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateReceiveAccounts::try_from(accounts)?;
+        let instruction_data = UpdateReceiveInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateReceive<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        unsafe { EscrowAccount::check(self.accounts.escrow, self.accounts.maker)? };
+
+        let mut data = self.accounts.escrow.try_borrow_mut_data()?;
+        let escrow = Escrow::load_mut(data.as_mut())?;
+
+        // An auction's start price can't drop below where it ends
+        if escrow.is_auction() && self.instruction_data.receive < escrow.end_price {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        escrow.set_receive(self.instruction_data.receive);
+
+        Ok(())
+    }
+}
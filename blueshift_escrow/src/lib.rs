@@ -8,6 +8,9 @@ use pinocchio::{
 entrypoint!(process_instruction);
 nostd_panic_handler!();
 
+pub mod errors;
+pub use errors::*;
+
 pub mod instructions;
 pub use instructions::*;
 
@@ -28,8 +31,14 @@ fn process_instruction(
 ) -> ProgramResult {
     match instruction_data.split_first() {
         Some((Make::DISCRIMINATOR, data)) => Make::try_from((data, accounts))?.process(),
-        Some((Take::DISCRIMINATOR, _)) => Take::try_from(accounts)?.process(),
+        Some((Take::DISCRIMINATOR, data)) => Take::try_from((data, accounts))?.process(),
         Some((Refund::DISCRIMINATOR, _)) => Refund::try_from(accounts)?.process(),
+        Some((GetEscrow::DISCRIMINATOR, _)) => GetEscrow::try_from(accounts)?.process(),
+        Some((ReclaimExcess::DISCRIMINATOR, _)) => ReclaimExcess::try_from(accounts)?.process(),
+        Some((UpdateReceive::DISCRIMINATOR, data)) => {
+            UpdateReceive::try_from((data, accounts))?.process()
+        }
+        Some((RefundBatch::DISCRIMINATOR, _)) => RefundBatch::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -1,29 +1,77 @@
 use core::mem::size_of;
-use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{create_program_address, Pubkey},
+};
+
+use crate::errors::EscrowError;
 
 #[repr(C)]
 pub struct Escrow {
+    /// Layout version, `Escrow::VERSION` for every escrow `make` writes.
+    pub version: u8,
+    /// Keeps the 8-byte fields below aligned.
+    _padding: [u8; 7],
     pub seed: u64,
     pub maker: Pubkey,
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
     pub receive: u64,
+    pub deposit: u64,
+    /// Unix timestamp after which the offer can no longer be taken, or zero
+    /// for an offer that never expires.
+    pub expiry: i64,
+    /// Dutch-auction terms: the price falls linearly from `receive` at
+    /// `start_time` to `end_price` at `end_time`. A zero `end_time` keeps
+    /// the price fixed at `receive`.
+    pub end_price: u64,
+    pub start_time: i64,
+    pub end_time: i64,
     pub bump: [u8; 1],
+    /// Non-zero when `mint_b` stands for native SOL, which the taker pays in
+    /// lamports rather than from a token account.
+    pub is_native_b: u8,
+    pub memo: [u8; 32],
+    pub allowed_taker: Pubkey,
+    /// Cut of each take's mint_b, in basis points, paid to `fee_recipient`
+    /// out of what the maker receives.
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
 }
 
 impl Escrow {
-    pub const LEN: usize = size_of::<u64>()
+    pub const LEN: usize = size_of::<u8>()
+        + size_of::<[u8; 7]>()
+        + size_of::<u64>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<u64>()
-        + size_of::<[u8; 1]>();
+        + size_of::<u64>()
+        + size_of::<i64>()
+        + size_of::<u64>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<[u8; 1]>()
+        + size_of::<u8>()
+        + size_of::<[u8; 32]>()
+        + size_of::<Pubkey>()
+        + size_of::<u16>()
+        + size_of::<Pubkey>();
+
+    /// Layout version stamped by `init`. Bump it whenever fields move, so
+    /// escrows in an older layout are refused rather than misread.
+    pub const VERSION: u8 = 1;
 
     #[inline(always)]
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
         if bytes.len() != Escrow::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        if bytes[0] != Escrow::VERSION {
+            return Err(EscrowError::VersionMismatch.into());
+        }
         Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
     }
 
@@ -32,9 +80,73 @@ impl Escrow {
         if bytes.len() != Escrow::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        if bytes[0] != Escrow::VERSION {
+            return Err(EscrowError::VersionMismatch.into());
+        }
         Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
     }
 
+    /// As `load_mut`, for a freshly created account: stamps the current
+    /// `VERSION` instead of checking it.
+    #[inline(always)]
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Escrow::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytes[0] = Escrow::VERSION;
+        Escrow::load_mut(bytes)
+    }
+
+    /// Loads the escrow held by `account`, checking it is owned by this
+    /// program, sized as an `Escrow`, and sits at the PDA its stored seed,
+    /// maker and bump derive.
+    ///
+    /// # Safety
+    ///
+    /// The account data must not be mutably borrowed while the returned
+    /// reference is in use.
+    #[inline(always)]
+    pub unsafe fn from_account_info(account: &AccountInfo) -> Result<&Self, ProgramError> {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let escrow = Escrow::load(account.borrow_data_unchecked())?;
+        let escrow_key = create_program_address(
+            &[
+                b"escrow",
+                &escrow.maker,
+                &escrow.seed.to_le_bytes(),
+                &escrow.bump,
+            ],
+            &crate::ID,
+        )?;
+        if &escrow_key != account.key() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(escrow)
+    }
+
+    /// Returns the memo, or `None` if it was left zeroed at `make`.
+    #[inline(always)]
+    pub fn memo(&self) -> Option<&[u8; 32]> {
+        if self.memo.iter().all(|&b| b == 0) {
+            None
+        } else {
+            Some(&self.memo)
+        }
+    }
+
+    /// Returns the only key allowed to take, or `None` if it was left zeroed
+    /// at `make` and anyone may.
+    #[inline(always)]
+    pub fn allowed_taker(&self) -> Option<&Pubkey> {
+        if self.allowed_taker.iter().all(|&b| b == 0) {
+            None
+        } else {
+            Some(&self.allowed_taker)
+        }
+    }
+
     #[inline(always)]
     pub fn set_inner(
         &mut self,
@@ -52,4 +164,76 @@ impl Escrow {
         self.receive = receive;
         self.bump = bump;
     }
+
+    /// Whether the offer's expiry, if it has one, has passed at `now`.
+    #[inline(always)]
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry != 0 && now > self.expiry
+    }
+
+    #[inline(always)]
+    pub fn set_receive(&mut self, receive: u64) {
+        self.receive = receive;
+    }
+
+    /// Whether the escrow is priced as a Dutch auction.
+    #[inline(always)]
+    pub fn is_auction(&self) -> bool {
+        self.end_time != 0
+    }
+
+    /// The mint_b price of the whole remaining deposit at `now`: `receive`
+    /// until `start_time`, `end_price` from `end_time` on, and linear in
+    /// between, rounded up in the maker's favor. Never above `receive`.
+    #[inline(always)]
+    pub fn current_receive(&self, now: i64) -> u64 {
+        if !self.is_auction() || now <= self.start_time {
+            return self.receive;
+        }
+        if now >= self.end_time {
+            return self.end_price.min(self.receive);
+        }
+        let drop = self.receive.saturating_sub(self.end_price) as u128;
+        let elapsed = now.abs_diff(self.start_time) as u128;
+        let duration = self.end_time.abs_diff(self.start_time) as u128;
+        self.receive - (drop * elapsed / duration) as u64
+    }
+
+    #[inline(always)]
+    pub fn set_deposit(&mut self, deposit: u64) {
+        self.deposit = deposit;
+    }
+
+    #[inline(always)]
+    pub fn set_auction(&mut self, end_price: u64, start_time: i64, end_time: i64) {
+        self.end_price = end_price;
+        self.start_time = start_time;
+        self.end_time = end_time;
+    }
+
+    #[inline(always)]
+    pub fn set_expiry(&mut self, expiry: i64) {
+        self.expiry = expiry;
+    }
+
+    #[inline(always)]
+    pub fn set_native_b(&mut self, is_native_b: bool) {
+        self.is_native_b = is_native_b as u8;
+    }
+
+    #[inline(always)]
+    pub fn set_memo(&mut self, memo: [u8; 32]) {
+        self.memo = memo;
+    }
+
+    #[inline(always)]
+    pub fn set_fee(&mut self, fee_bps: u16, fee_recipient: Pubkey) {
+        self.fee_bps = fee_bps;
+        self.fee_recipient = fee_recipient;
+    }
+
+    #[inline(always)]
+    pub fn set_allowed_taker(&mut self, allowed_taker: Pubkey) {
+        self.allowed_taker = allowed_taker;
+    }
 }
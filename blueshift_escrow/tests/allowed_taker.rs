@@ -0,0 +1,68 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+#[test]
+fn make_stores_the_allowed_taker() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.make(&MakeData {
+            allowed_taker: Some(offer.taker),
+            ..offer.make_data(RECEIVE, DEPOSIT)
+        }),
+        &offer.make_accounts(DEPOSIT),
+        &[Check::success()],
+    );
+    let escrow = escrow_of(result.get_account(&offer.escrow).unwrap());
+    assert_eq!(escrow.allowed_taker(), Some(&offer.taker.to_bytes()));
+}
+
+#[test]
+fn the_allowed_taker_can_take() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| {
+        escrow.set_allowed_taker(offer.taker.to_bytes())
+    });
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn any_other_taker_is_rejected() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| {
+        escrow.set_allowed_taker(Pubkey::new_unique().to_bytes())
+    });
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn an_offer_without_an_allowed_taker_is_open_to_anyone() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const DEPOSIT: u64 = 1_000;
+const START_PRICE: u64 = 1_000;
+const END_PRICE: u64 = 400;
+const START: i64 = 1_700_000_000;
+const END: i64 = START + 100;
+
+/// What the maker receives for a whole take at `now`.
+fn price_at(now: i64) -> u64 {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = now;
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(START_PRICE, DEPOSIT, |escrow| {
+        escrow.set_auction(END_PRICE, START, END)
+    });
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.taker_ata_a).unwrap()),
+        DEPOSIT
+    );
+    token_amount(result.get_account(&offer.maker_ata_b).unwrap())
+}
+
+#[test]
+fn auction_starts_at_the_start_price() {
+    assert_eq!(price_at(START), START_PRICE);
+}
+
+#[test]
+fn auction_decays_linearly_through_the_window() {
+    assert_eq!(price_at(START + 50), 700);
+}
+
+#[test]
+fn auction_holds_the_end_price_after_the_window() {
+    assert_eq!(price_at(END + 1_000), END_PRICE);
+}
+
+#[test]
+fn auction_rejects_a_partial_fill() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = START;
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(START_PRICE, DEPOSIT, |escrow| {
+        escrow.set_auction(END_PRICE, START, END)
+    });
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(Some(START_PRICE / 2), &[]),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
@@ -0,0 +1,61 @@
+//! `ProgramAccount::close` runs inside Take and Refund after their token
+//! CPIs, which would fail on an overflowing destination first, so these
+//! tests drive it natively.
+
+mod common;
+
+use blueshift_escrow::ProgramAccount;
+use common::native::{accounts, input as native_input, Input};
+use pinocchio::program_error::ProgramError;
+
+const ACCOUNT_LAMPORTS: u64 = 2_000_000;
+
+/// A program-owned account holding `data`, followed by a destination
+/// holding `destination_lamports`.
+fn input(data: &[u8], destination_lamports: u64) -> Vec<u64> {
+    native_input(&[
+        Input {
+            key: [1; 32],
+            owner: blueshift_escrow::ID,
+            lamports: ACCOUNT_LAMPORTS,
+            data,
+            is_signer: false,
+        },
+        Input {
+            key: [2; 32],
+            owner: [0; 32],
+            lamports: destination_lamports,
+            data: &[],
+            is_signer: true,
+        },
+    ])
+}
+
+#[test]
+fn close_moves_the_lamports_and_wipes_the_account() {
+    let mut input = input(&[7; 16], 1_000_000);
+    let [account, destination] = accounts(&mut input);
+
+    ProgramAccount::close(&account, &destination).unwrap();
+
+    assert_eq!(account.lamports(), 0);
+    assert_eq!(destination.lamports(), 1_000_000 + ACCOUNT_LAMPORTS);
+    assert!(account
+        .try_borrow_data()
+        .unwrap()
+        .iter()
+        .all(|&byte| byte == 0));
+    assert!(account.is_owned_by(&pinocchio_system::ID));
+}
+
+#[test]
+fn close_rejects_a_destination_that_would_overflow() {
+    let mut input = input(&[7; 16], u64::MAX - ACCOUNT_LAMPORTS + 1);
+    let [account, destination] = accounts(&mut input);
+
+    assert_eq!(
+        ProgramAccount::close(&account, &destination),
+        Err(ProgramError::ArithmeticOverflow)
+    );
+    assert_eq!(destination.lamports(), u64::MAX - ACCOUNT_LAMPORTS + 1);
+}
@@ -0,0 +1,417 @@
+//! Account builders shared by the escrow tests. The program must be built
+//! with `cargo build-sbf` first, so `target/deploy` holds its `.so`.
+
+#![allow(dead_code)]
+
+pub mod native;
+
+use std::path::Path;
+
+use blueshift_escrow::Escrow;
+use mollusk_svm::{program::keyed_account_for_system_program, result::InstructionResult, Mollusk};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    rent::Rent,
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(blueshift_escrow::ID);
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ATA_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Sizes of the legacy token layouts, which Token-2022 shares.
+pub const MINT_LEN: usize = 82;
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// The program as built by `cargo build-sbf`, relative to the crate root.
+const PROGRAM_SO: &str = "target/deploy/blueshift_escrow";
+
+pub fn mollusk() -> Mollusk {
+    assert!(
+        Path::new(PROGRAM_SO).with_extension("so").exists(),
+        "{PROGRAM_SO}.so is missing, run `cargo build-sbf` before `cargo test`"
+    );
+    let mut mollusk = Mollusk::new(&PROGRAM_ID, PROGRAM_SO);
+    mollusk_svm_programs_token::token::add_program(&mut mollusk);
+    mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+    mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+    mollusk
+}
+
+pub fn rent_exempt(len: usize) -> u64 {
+    Rent::default().minimum_balance(len)
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::ID)
+}
+
+/// An initialized mint with no authorities, owned by `token_program`.
+pub fn mint(decimals: u8, token_program: &Pubkey) -> Account {
+    let mut data = vec![0u8; MINT_LEN];
+    data[36..44].copy_from_slice(&u64::MAX.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1;
+    Account {
+        lamports: rent_exempt(MINT_LEN),
+        data,
+        owner: *token_program,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// An initialized token account holding `amount` of `mint` for `owner`.
+pub fn token_account(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    token_program: &Pubkey,
+) -> Account {
+    let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+    data[0..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = 1;
+    Account {
+        lamports: rent_exempt(TOKEN_ACCOUNT_LEN),
+        data,
+        owner: *token_program,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Balance of a legacy or Token-2022 token account.
+pub fn token_amount(account: &Account) -> u64 {
+    u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ATA_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn escrow_address(maker: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"escrow", maker.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// A fixed-price escrow as `Make` would write it.
+pub fn escrow(
+    maker: &Pubkey,
+    seed: u64,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    receive: u64,
+    deposit: u64,
+) -> Account {
+    escrow_with(maker, seed, mint_a, mint_b, receive, deposit, |_| {})
+}
+
+/// As `escrow`, with `configure` adjusting the escrow's optional terms.
+pub fn escrow_with(
+    maker: &Pubkey,
+    seed: u64,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    receive: u64,
+    deposit: u64,
+    configure: impl FnOnce(&mut Escrow),
+) -> Account {
+    let (_, bump) = escrow_address(maker, seed);
+    let mut data = vec![0u8; Escrow::LEN];
+    let escrow = Escrow::init(&mut data).unwrap();
+    escrow.set_inner(
+        seed,
+        maker.to_bytes(),
+        mint_a.to_bytes(),
+        mint_b.to_bytes(),
+        receive,
+        [bump],
+    );
+    escrow.set_deposit(deposit);
+    configure(escrow);
+    Account {
+        lamports: rent_exempt(Escrow::LEN),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Reads the `Escrow` held by a resulting account.
+pub fn escrow_of(account: &Account) -> &Escrow {
+    Escrow::load(&account.data).unwrap()
+}
+
+/// Replaces the account at `key` in a fixture list.
+pub fn set_account(accounts: &mut [(Pubkey, Account)], key: &Pubkey, account: Account) {
+    accounts.iter_mut().find(|(k, _)| k == key).unwrap().1 = account;
+}
+
+/// Carries an instruction's resulting accounts over into `accounts`, for the
+/// next instruction in a sequence.
+pub fn apply(accounts: &mut [(Pubkey, Account)], result: &InstructionResult) {
+    for (key, account) in &result.resulting_accounts {
+        if let Some(entry) = accounts.iter_mut().find(|(k, _)| k == key) {
+            entry.1 = account.clone();
+        }
+    }
+}
+
+/// Make data: the fixed terms, then each optional field up to the last one
+/// set, with any earlier unset fields sent as zeros.
+#[derive(Clone, Copy, Default)]
+pub struct MakeData {
+    pub seed: u64,
+    pub receive: u64,
+    pub amount: u64,
+    pub memo: Option<[u8; 32]>,
+    pub expiry: Option<i64>,
+    pub allowed_taker: Option<Pubkey>,
+    pub fee: Option<(u16, Pubkey)>,
+    pub auction: Option<(u64, i64, i64)>,
+}
+
+impl MakeData {
+    pub fn bytes(&self) -> Vec<u8> {
+        let mut data = vec![0u8];
+        data.extend_from_slice(&self.seed.to_le_bytes());
+        data.extend_from_slice(&self.receive.to_le_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+
+        let optional = [
+            self.memo.is_some(),
+            self.expiry.is_some(),
+            self.allowed_taker.is_some(),
+            self.fee.is_some(),
+            self.auction.is_some(),
+        ];
+        let fields = optional.iter().rposition(|&set| set).map_or(0, |i| i + 1);
+        if fields > 0 {
+            data.extend_from_slice(&self.memo.unwrap_or_default());
+        }
+        if fields > 1 {
+            data.extend_from_slice(&self.expiry.unwrap_or_default().to_le_bytes());
+        }
+        if fields > 2 {
+            data.extend_from_slice(self.allowed_taker.unwrap_or_default().as_ref());
+        }
+        if fields > 3 {
+            let (fee_bps, fee_recipient) = self.fee.unwrap_or_default();
+            data.extend_from_slice(&fee_bps.to_le_bytes());
+            data.extend_from_slice(fee_recipient.as_ref());
+        }
+        if fields > 4 {
+            let (end_price, start_time, end_time) = self.auction.unwrap_or_default();
+            data.extend_from_slice(&end_price.to_le_bytes());
+            data.extend_from_slice(&start_time.to_le_bytes());
+            data.extend_from_slice(&end_time.to_le_bytes());
+        }
+        data
+    }
+}
+
+/// One offer's accounts under the legacy token program: the maker's side,
+/// the escrow and its vault, and a taker's side.
+pub struct Offer {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub escrow: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub maker_ata_a: Pubkey,
+    pub maker_ata_b: Pubkey,
+    pub vault: Pubkey,
+    pub taker: Pubkey,
+    pub taker_ata_a: Pubkey,
+    pub taker_ata_b: Pubkey,
+}
+
+impl Offer {
+    pub const SEED: u64 = 7;
+
+    pub fn new() -> Self {
+        let maker = Pubkey::new_unique();
+        let taker = Pubkey::new_unique();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let (escrow, _) = escrow_address(&maker, Self::SEED);
+        Self {
+            seed: Self::SEED,
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            maker_ata_a: associated_token_address(&maker, &mint_a, &TOKEN_PROGRAM_ID),
+            maker_ata_b: associated_token_address(&maker, &mint_b, &TOKEN_PROGRAM_ID),
+            vault: associated_token_address(&escrow, &mint_a, &TOKEN_PROGRAM_ID),
+            taker,
+            taker_ata_a: associated_token_address(&taker, &mint_a, &TOKEN_PROGRAM_ID),
+            taker_ata_b: associated_token_address(&taker, &mint_b, &TOKEN_PROGRAM_ID),
+        }
+    }
+
+    /// Make data for this offer's seed.
+    pub fn make_data(&self, receive: u64, amount: u64) -> MakeData {
+        MakeData {
+            seed: self.seed,
+            receive,
+            amount,
+            ..Default::default()
+        }
+    }
+
+    /// Accounts before `Make`, with the maker holding `balance` of mint_a.
+    pub fn make_accounts(&self, balance: u64) -> Vec<(Pubkey, Account)> {
+        vec![
+            (self.maker, system_account(1_000_000_000)),
+            (self.escrow, system_account(0)),
+            (self.mint_a, mint(6, &TOKEN_PROGRAM_ID)),
+            (self.mint_b, mint(6, &TOKEN_PROGRAM_ID)),
+            (
+                self.maker_ata_a,
+                token_account(&self.mint_a, &self.maker, balance, &TOKEN_PROGRAM_ID),
+            ),
+            (self.vault, system_account(0)),
+            keyed_account_for_system_program(),
+            mollusk_svm_programs_token::token::keyed_account(),
+            mollusk_svm_programs_token::associated_token::keyed_account(),
+        ]
+    }
+
+    /// Accounts after `Make` of `deposit` for `receive`, `configure`
+    /// adjusting the escrow, with the taker holding `receive` of mint_b and
+    /// the ATAs `Take` and `Refund` create not yet made.
+    pub fn made_accounts(
+        &self,
+        receive: u64,
+        deposit: u64,
+        configure: impl FnOnce(&mut Escrow),
+    ) -> Vec<(Pubkey, Account)> {
+        vec![
+            (self.maker, system_account(1_000_000_000)),
+            (
+                self.escrow,
+                escrow_with(
+                    &self.maker,
+                    self.seed,
+                    &self.mint_a,
+                    &self.mint_b,
+                    receive,
+                    deposit,
+                    configure,
+                ),
+            ),
+            (self.mint_a, mint(6, &TOKEN_PROGRAM_ID)),
+            (self.mint_b, mint(6, &TOKEN_PROGRAM_ID)),
+            (
+                self.vault,
+                token_account(&self.mint_a, &self.escrow, deposit, &TOKEN_PROGRAM_ID),
+            ),
+            (self.maker_ata_a, system_account(0)),
+            (self.maker_ata_b, system_account(0)),
+            (self.taker, system_account(1_000_000_000)),
+            (self.taker_ata_a, system_account(0)),
+            (
+                self.taker_ata_b,
+                token_account(&self.mint_b, &self.taker, receive, &TOKEN_PROGRAM_ID),
+            ),
+            keyed_account_for_system_program(),
+            mollusk_svm_programs_token::token::keyed_account(),
+            mollusk_svm_programs_token::associated_token::keyed_account(),
+        ]
+    }
+
+    pub fn make(&self, data: &MakeData) -> Instruction {
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data.bytes(),
+            vec![
+                AccountMeta::new(self.maker, true),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.mint_a, false),
+                AccountMeta::new_readonly(self.mint_b, false),
+                AccountMeta::new(self.maker_ata_a, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+            ],
+        )
+    }
+
+    /// A `Take` filling `fill_amount`, or the rest of the escrow when `None`,
+    /// with `trailing` after the fixed accounts.
+    pub fn take(&self, fill_amount: Option<u64>, trailing: &[AccountMeta]) -> Instruction {
+        let mut data = vec![1u8];
+        if let Some(fill_amount) = fill_amount {
+            data.extend_from_slice(&fill_amount.to_le_bytes());
+        }
+        let mut metas = vec![
+            AccountMeta::new(self.taker, true),
+            AccountMeta::new(self.maker, false),
+            AccountMeta::new(self.escrow, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(self.taker_ata_a, false),
+            AccountMeta::new(self.taker_ata_b, false),
+            AccountMeta::new(self.maker_ata_b, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ];
+        metas.extend_from_slice(trailing);
+        Instruction::new_with_bytes(PROGRAM_ID, &data, metas)
+    }
+
+    pub fn refund(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[2],
+            vec![
+                AccountMeta::new(self.maker, true),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.mint_a, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.maker_ata_a, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+            ],
+        )
+    }
+
+    /// An `UpdateReceive` to `receive`, signed by `signer`.
+    pub fn update_receive(&self, signer: &Pubkey, receive: u64) -> Instruction {
+        let mut data = vec![5u8];
+        data.extend_from_slice(&receive.to_le_bytes());
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &data,
+            vec![
+                AccountMeta::new(*signer, true),
+                AccountMeta::new(self.escrow, false),
+            ],
+        )
+    }
+
+    pub fn get_escrow(&self) -> Instruction {
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[3],
+            vec![AccountMeta::new_readonly(self.escrow, false)],
+        )
+    }
+}
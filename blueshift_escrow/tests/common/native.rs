@@ -0,0 +1,57 @@
+//! A runtime-serialized input buffer, for driving helpers natively where no
+//! instruction exercises them in isolation. CPIs are no-ops off-chain.
+
+use core::mem::MaybeUninit;
+
+use pinocchio::{
+    account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+    entrypoint::deserialize,
+};
+
+/// What the runtime leaves in each account's realloc region.
+pub const STALE: u8 = 0xAB;
+
+/// One account as the runtime serializes it, always writable.
+pub struct Input<'a> {
+    pub key: [u8; 32],
+    pub owner: [u8; 32],
+    pub lamports: u64,
+    pub data: &'a [u8],
+    pub is_signer: bool,
+}
+
+/// The runtime's input buffer for `accounts`, as 8-byte aligned words.
+pub fn input(accounts: &[Input]) -> Vec<u64> {
+    let mut bytes = Vec::new();
+    for account in accounts {
+        bytes.extend_from_slice(&[u8::MAX, account.is_signer as u8, 1, 0]);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&account.key);
+        bytes.extend_from_slice(&account.owner);
+        bytes.extend_from_slice(&account.lamports.to_le_bytes());
+        bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(account.data);
+        bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, STALE);
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // rent_epoch
+    }
+    bytes.extend_from_slice(&0u64.to_le_bytes()); // instruction data
+    bytes.extend_from_slice(&blueshift_escrow::ID);
+
+    let mut input = vec![accounts.len() as u64];
+    input.extend(
+        bytes
+            .chunks(8)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap())),
+    );
+    input
+}
+
+/// The `N` accounts held by `input`.
+pub fn accounts<const N: usize>(input: &mut [u64]) -> [AccountInfo; N] {
+    let mut accounts = [const { MaybeUninit::<AccountInfo>::uninit() }; N];
+    unsafe {
+        deserialize::<N>(input.as_mut_ptr() as *mut u8, &mut accounts);
+        accounts.map(|account| account.assume_init())
+    }
+}
@@ -0,0 +1,34 @@
+//! Decoding custom error codes back into `EscrowError`, as clients do. Run
+//! with `--features client`.
+
+#![cfg(feature = "client")]
+
+use blueshift_escrow::EscrowError;
+use pinocchio::program_error::ProgramError;
+
+const VARIANTS: [EscrowError; 4] = [
+    EscrowError::ZeroSeed,
+    EscrowError::Expired,
+    EscrowError::VersionMismatch,
+    EscrowError::TooManyItems,
+];
+
+#[test]
+fn every_variant_round_trips_through_its_custom_code() {
+    for variant in VARIANTS {
+        let ProgramError::Custom(code) = ProgramError::from(variant) else {
+            panic!("{variant:?} did not convert to a custom error");
+        };
+        assert_eq!(EscrowError::try_from(code), Ok(variant));
+    }
+}
+
+#[test]
+fn unassigned_codes_are_rejected() {
+    for code in [0, 5, u32::MAX] {
+        assert_eq!(
+            EscrowError::try_from(code),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+#[test]
+fn an_escrow_at_its_own_pda_loads() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.maker, 2 * RECEIVE),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn an_escrow_owned_by_another_program_is_rejected() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+    let escrow = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &offer.escrow)
+        .unwrap();
+    escrow.1.owner = Pubkey::new_unique();
+
+    mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.maker, 2 * RECEIVE),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn an_escrow_away_from_the_pda_its_data_derives_is_rejected() {
+    let mollusk = mollusk();
+    let mut offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    // The same data, moved to the maker's escrow for another seed
+    let (elsewhere, _) = escrow_address(&offer.maker, Offer::SEED + 1);
+    let escrow = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &offer.escrow)
+        .unwrap();
+    escrow.0 = elsewhere;
+    offer.escrow = elsewhere;
+
+    mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.maker, 2 * RECEIVE),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
+
+#[test]
+fn a_refund_signed_by_someone_other_than_the_maker_is_rejected() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    let mut refund = offer.refund();
+    refund.accounts[0] = AccountMeta::new(offer.taker, true);
+
+    mollusk.process_and_validate_instruction(
+        &refund,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn a_take_paying_someone_other_than_the_maker_is_rejected() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let impostor = Pubkey::new_unique();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+    accounts.push((impostor, system_account(1_000_000_000)));
+
+    let mut take = offer.take(None, &[]);
+    take.accounts[1] = AccountMeta::new(impostor, false);
+
+    mollusk.process_and_validate_instruction(
+        &take,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
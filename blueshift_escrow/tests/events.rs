@@ -0,0 +1,53 @@
+//! The make, take and refund logs share `encode_escrow_event`'s layout.
+//! Logs aren't captured by the test harness, so these tests check the
+//! encoder natively against each instruction's arguments.
+
+use blueshift_escrow::{encode_escrow_event, Escrow, ESCROW_EVENT_LEN};
+
+const SEED: u64 = 7;
+const MAKER: [u8; 32] = [1; 32];
+const MINT_A: [u8; 32] = [2; 32];
+const MINT_B: [u8; 32] = [3; 32];
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+/// The record an escrow made with these terms logs for `amount_a` and
+/// `amount_b`, laid out by hand.
+fn expected(amount_a: u64, amount_b: u64) -> Vec<u8> {
+    let mut event = SEED.to_le_bytes().to_vec();
+    event.extend_from_slice(&MAKER);
+    event.extend_from_slice(&MINT_A);
+    event.extend_from_slice(&MINT_B);
+    event.extend_from_slice(&amount_a.to_le_bytes());
+    event.extend_from_slice(&amount_b.to_le_bytes());
+    event
+}
+
+fn escrow_data() -> Vec<u8> {
+    let mut data = vec![0u8; Escrow::LEN];
+    Escrow::init(&mut data)
+        .unwrap()
+        .set_inner(SEED, MAKER, MINT_A, MINT_B, RECEIVE, [255]);
+    data
+}
+
+#[test]
+fn make_event_carries_the_deposit_and_the_price() {
+    let data = escrow_data();
+    let escrow = Escrow::load(&data).unwrap();
+
+    let event = encode_escrow_event(escrow, DEPOSIT, RECEIVE);
+    assert_eq!(event.len(), ESCROW_EVENT_LEN);
+    assert_eq!(event.to_vec(), expected(DEPOSIT, RECEIVE));
+}
+
+#[test]
+fn take_event_carries_the_amounts_the_fill_moved() {
+    let data = escrow_data();
+    let escrow = Escrow::load(&data).unwrap();
+
+    // A half fill moves half of each side; the header is the escrow's alone
+    let event = encode_escrow_event(escrow, DEPOSIT / 2, RECEIVE / 2);
+    assert_eq!(event.to_vec(), expected(DEPOSIT / 2, RECEIVE / 2));
+    assert_ne!(event.to_vec(), expected(DEPOSIT, RECEIVE));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use blueshift_escrow::EscrowError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+const NOW: i64 = 1_700_000_000;
+
+#[test]
+fn make_stores_the_expiry() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.make(&MakeData {
+            expiry: Some(NOW),
+            ..offer.make_data(RECEIVE, DEPOSIT)
+        }),
+        &offer.make_accounts(DEPOSIT),
+        &[Check::success()],
+    );
+    assert_eq!(
+        escrow_of(result.get_account(&offer.escrow).unwrap()).expiry,
+        NOW
+    );
+}
+
+#[test]
+fn take_before_the_expiry_succeeds() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = NOW;
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| escrow.set_expiry(NOW));
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn take_after_the_expiry_is_rejected() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = NOW + 1;
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| escrow.set_expiry(NOW));
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            EscrowError::Expired as u32,
+        ))],
+    );
+}
+
+#[test]
+fn take_without_an_expiry_never_lapses() {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = NOW + 365 * 24 * 60 * 60;
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| escrow.set_expiry(0));
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account, instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 10_000;
+const FEE_BPS: u16 = 250;
+const FEE: u64 = 250;
+
+/// A made offer charging `fee_bps` to a recipient holding an empty mint_b
+/// ATA, returned with that ATA.
+fn setup(fee_bps: u16) -> (Offer, Pubkey, Vec<(Pubkey, Account)>) {
+    let offer = Offer::new();
+    let recipient = Pubkey::new_unique();
+    let recipient_ata = associated_token_address(&recipient, &offer.mint_b, &TOKEN_PROGRAM_ID);
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| {
+        escrow.set_fee(fee_bps, recipient.to_bytes())
+    });
+    accounts.push((
+        recipient_ata,
+        token_account(&offer.mint_b, &recipient, 0, &TOKEN_PROGRAM_ID),
+    ));
+    (offer, recipient_ata, accounts)
+}
+
+#[test]
+fn zero_fee_pays_the_maker_in_full() {
+    let mollusk = mollusk();
+    let (offer, recipient_ata, accounts) = setup(0);
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[AccountMeta::new(recipient_ata, false)]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.maker_ata_b).unwrap()),
+        RECEIVE
+    );
+    assert_eq!(token_amount(result.get_account(&recipient_ata).unwrap()), 0);
+    assert_eq!(
+        token_amount(result.get_account(&offer.taker_ata_b).unwrap()),
+        0
+    );
+}
+
+#[test]
+fn fee_is_carved_out_of_the_makers_share() {
+    let mollusk = mollusk();
+    let (offer, recipient_ata, accounts) = setup(FEE_BPS);
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[AccountMeta::new(recipient_ata, false)]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.maker_ata_b).unwrap()),
+        RECEIVE - FEE
+    );
+    assert_eq!(
+        token_amount(result.get_account(&recipient_ata).unwrap()),
+        FEE
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.taker_ata_b).unwrap()),
+        0
+    );
+}
+
+#[test]
+fn fee_without_its_destination_is_rejected() {
+    let mollusk = mollusk();
+    let (offer, _, accounts) = setup(FEE_BPS);
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
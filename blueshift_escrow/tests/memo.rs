@@ -0,0 +1,69 @@
+mod common;
+
+use blueshift_escrow::Escrow;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const AMOUNT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+const MEMO: [u8; 32] = *b"order-2f9c1e7a-0000-0000-0000-01";
+
+#[test]
+fn make_stores_the_memo_and_get_escrow_returns_it() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let data = MakeData {
+        memo: Some(MEMO),
+        ..offer.make_data(RECEIVE, AMOUNT)
+    };
+
+    let made = mollusk.process_and_validate_instruction(
+        &offer.make(&data),
+        &offer.make_accounts(AMOUNT),
+        &[Check::success()],
+    );
+    let escrow = made.get_account(&offer.escrow).unwrap();
+    assert_eq!(escrow_of(escrow).memo(), Some(&MEMO));
+
+    // The read path hands back the stored bytes, memo included
+    let read = mollusk.process_and_validate_instruction(
+        &offer.get_escrow(),
+        &made.resulting_accounts,
+        &[Check::success(), Check::return_data(&escrow.data)],
+    );
+    assert_eq!(Escrow::load(&read.return_data).unwrap().memo(), Some(&MEMO));
+}
+
+#[test]
+fn make_without_a_memo_stores_none() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+
+    let made = mollusk.process_and_validate_instruction(
+        &offer.make(&offer.make_data(RECEIVE, AMOUNT)),
+        &offer.make_accounts(AMOUNT),
+        &[Check::success()],
+    );
+    assert_eq!(
+        escrow_of(made.get_account(&offer.escrow).unwrap()).memo(),
+        None
+    );
+}
+
+#[test]
+fn make_rejects_a_truncated_memo() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut instruction = offer.make(&MakeData {
+        memo: Some(MEMO),
+        ..offer.make_data(RECEIVE, AMOUNT)
+    });
+    instruction.data.truncate(instruction.data.len() - 16);
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &offer.make_accounts(AMOUNT),
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use blueshift_escrow::NATIVE_MINT;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, program_error::ProgramError, pubkey::Pubkey};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500_000;
+/// Positions of mint_b and taker_ata_b in Take's account list.
+const MINT_B: usize = 4;
+const TAKER_ATA_B: usize = 7;
+
+/// An offer receiving native SOL, already made.
+fn setup() -> (Offer, Vec<(Pubkey, Account)>) {
+    let mut offer = Offer::new();
+    offer.mint_b = Pubkey::new_from_array(NATIVE_MINT);
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |escrow| escrow.set_native_b(true));
+    (offer, accounts)
+}
+
+fn lamports(accounts: &[(Pubkey, Account)], key: &Pubkey) -> u64 {
+    accounts.iter().find(|(k, _)| k == key).unwrap().1.lamports
+}
+
+#[test]
+fn make_flags_a_native_receive_side() {
+    let mollusk = mollusk();
+    let mut offer = Offer::new();
+    offer.mint_b = Pubkey::new_from_array(NATIVE_MINT);
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.make(&offer.make_data(RECEIVE, DEPOSIT)),
+        &offer.make_accounts(DEPOSIT),
+        &[Check::success()],
+    );
+    assert_eq!(
+        escrow_of(result.get_account(&offer.escrow).unwrap()).is_native_b,
+        1
+    );
+}
+
+#[test]
+fn take_pays_a_native_receive_side_in_lamports() {
+    let mollusk = mollusk();
+    let (offer, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // The maker gets the price in lamports, plus the closed accounts' rent
+    let maker = lamports(&accounts, &offer.maker)
+        + RECEIVE
+        + lamports(&accounts, &offer.vault)
+        + lamports(&accounts, &offer.escrow);
+    assert_eq!(result.get_account(&offer.maker).unwrap().lamports, maker);
+    assert_eq!(result.get_account(&offer.maker_ata_b).unwrap().lamports, 0);
+    assert_eq!(
+        token_amount(result.get_account(&offer.taker_ata_a).unwrap()),
+        DEPOSIT
+    );
+}
+
+#[test]
+fn take_pays_an_spl_receive_side_in_tokens() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.maker_ata_b).unwrap()),
+        RECEIVE
+    );
+}
+
+#[test]
+fn take_rejects_an_spl_mint_b_for_a_native_escrow() {
+    let mollusk = mollusk();
+    let (offer, mut accounts) = setup();
+    let spl_mint = Pubkey::new_unique();
+    let spl_ata = associated_token_address(&offer.taker, &spl_mint, &TOKEN_PROGRAM_ID);
+    accounts.push((spl_mint, mint(6, &TOKEN_PROGRAM_ID)));
+    accounts.push((
+        spl_ata,
+        token_account(&spl_mint, &offer.taker, RECEIVE, &TOKEN_PROGRAM_ID),
+    ));
+
+    // A well-formed SPL side, which still isn't the escrow's
+    let mut instruction = offer.take(None, &[]);
+    instruction.accounts[MINT_B].pubkey = spl_mint;
+    instruction.accounts[TAKER_ATA_B].pubkey = spl_ata;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
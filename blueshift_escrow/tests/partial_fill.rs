@@ -0,0 +1,66 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+/// An odd deposit, so a half fill rounds the taker's share down.
+const DEPOSIT: u64 = 1_001;
+const RECEIVE: u64 = 500;
+
+#[test]
+fn half_fill_then_closing_fill_settles_the_whole_offer() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    // The first half pays half the deposit, rounded down, and stays open
+    let half = mollusk.process_and_validate_instruction(
+        &offer.take(Some(RECEIVE / 2), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(half.get_account(&offer.taker_ata_a).unwrap()),
+        500
+    );
+    assert_eq!(
+        token_amount(half.get_account(&offer.maker_ata_b).unwrap()),
+        250
+    );
+    assert_eq!(token_amount(half.get_account(&offer.vault).unwrap()), 501);
+    let escrow = escrow_of(half.get_account(&offer.escrow).unwrap());
+    let (receive, deposit) = (escrow.receive, escrow.deposit);
+    assert_eq!((receive, deposit), (RECEIVE / 2, 501));
+    apply(&mut accounts, &half);
+
+    // The closing fill sweeps the rounding dust and closes the escrow
+    let rest = mollusk.process_and_validate_instruction(
+        &offer.take(Some(RECEIVE / 2), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(rest.get_account(&offer.taker_ata_a).unwrap()),
+        DEPOSIT
+    );
+    assert_eq!(
+        token_amount(rest.get_account(&offer.maker_ata_b).unwrap()),
+        RECEIVE
+    );
+    assert_eq!(rest.get_account(&offer.vault).unwrap().lamports, 0);
+    assert_eq!(rest.get_account(&offer.escrow).unwrap().lamports, 0);
+}
+
+#[test]
+fn fill_beyond_the_remaining_receive_is_rejected() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    mollusk.process_and_validate_instruction(
+        &offer.take(Some(RECEIVE + 1), &[]),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
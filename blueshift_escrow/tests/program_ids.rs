@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+/// Positions of the system and token programs in Make's and Refund's
+/// account lists.
+const MAKE_SYSTEM_PROGRAM: usize = 6;
+const MAKE_TOKEN_PROGRAM: usize = 7;
+const REFUND_SYSTEM_PROGRAM: usize = 5;
+const REFUND_TOKEN_PROGRAM: usize = 6;
+
+#[test]
+fn refund_accepts_the_real_programs() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    let result =
+        mollusk.process_and_validate_instruction(&offer.refund(), &accounts, &[Check::success()]);
+    assert_eq!(
+        token_amount(result.get_account(&offer.maker_ata_a).unwrap()),
+        DEPOSIT
+    );
+}
+
+#[test]
+fn refund_rejects_a_substituted_system_program() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+    let impostor = Pubkey::new_unique();
+    accounts.push((impostor, system_account(0)));
+
+    let mut instruction = offer.refund();
+    instruction.accounts[REFUND_SYSTEM_PROGRAM].pubkey = impostor;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::IncorrectProgramId)],
+    );
+}
+
+#[test]
+fn refund_rejects_a_substituted_token_program() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+    let impostor = Pubkey::new_unique();
+    accounts.push((impostor, system_account(0)));
+
+    let mut instruction = offer.refund();
+    instruction.accounts[REFUND_TOKEN_PROGRAM].pubkey = impostor;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::IncorrectProgramId)],
+    );
+}
+
+#[test]
+fn make_rejects_substituted_programs() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.make_accounts(DEPOSIT);
+    let impostor = Pubkey::new_unique();
+    accounts.push((impostor, system_account(0)));
+
+    for index in [MAKE_SYSTEM_PROGRAM, MAKE_TOKEN_PROGRAM] {
+        let mut instruction = offer.make(&offer.make_data(RECEIVE, DEPOSIT));
+        instruction.accounts[index].pubkey = impostor;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::IncorrectProgramId)],
+        );
+    }
+}
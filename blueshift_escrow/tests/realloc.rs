@@ -0,0 +1,82 @@
+//! `ProgramAccount::realloc` has no instruction of its own yet, so these
+//! tests drive it natively over a runtime-serialized input buffer. The rent
+//! top-up is a system program CPI, which only moves lamports on-chain.
+
+mod common;
+
+use blueshift_escrow::ProgramAccount;
+use common::native::{accounts, input as native_input, Input};
+use pinocchio::{
+    account_info::MAX_PERMITTED_DATA_INCREASE, program_error::ProgramError, sysvars::rent::Rent,
+};
+
+const ACCOUNT_LEN: usize = 16;
+const GROWN_LEN: usize = 48;
+
+const RENT: Rent = Rent {
+    lamports_per_byte_year: 3_480,
+    exemption_threshold: 2.0,
+    burn_percent: 50,
+};
+
+/// A program-owned account holding `data`, followed by a funded, signing
+/// payer.
+fn input(data: &[u8], lamports: u64) -> Vec<u64> {
+    native_input(&[
+        Input {
+            key: [1; 32],
+            owner: blueshift_escrow::ID,
+            lamports,
+            data,
+            is_signer: false,
+        },
+        Input {
+            key: [2; 32],
+            owner: [0; 32],
+            lamports: 1_000_000_000,
+            data: &[],
+            is_signer: true,
+        },
+    ])
+}
+
+#[test]
+fn realloc_grows_the_account_and_zeroes_the_new_bytes() {
+    let mut input = input(&[7; ACCOUNT_LEN], RENT.minimum_balance(ACCOUNT_LEN));
+    let [account, payer] = accounts(&mut input);
+
+    ProgramAccount::realloc_with_rent(&account, GROWN_LEN, &payer, &RENT).unwrap();
+
+    let data = account.try_borrow_data().unwrap();
+    assert_eq!(data.len(), GROWN_LEN);
+    assert_eq!(&data[..ACCOUNT_LEN], &[7; ACCOUNT_LEN]);
+    assert!(data[ACCOUNT_LEN..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn realloc_rejects_shrinking_the_account() {
+    let mut input = input(&[7; ACCOUNT_LEN], RENT.minimum_balance(ACCOUNT_LEN));
+    let [account, payer] = accounts(&mut input);
+
+    assert_eq!(
+        ProgramAccount::realloc_with_rent(&account, ACCOUNT_LEN - 1, &payer, &RENT),
+        Err(ProgramError::InvalidRealloc)
+    );
+    assert_eq!(account.data_len(), ACCOUNT_LEN);
+}
+
+#[test]
+fn realloc_rejects_growth_past_the_permitted_increase() {
+    let mut input = input(&[7; ACCOUNT_LEN], RENT.minimum_balance(ACCOUNT_LEN));
+    let [account, payer] = accounts(&mut input);
+    let too_long = ACCOUNT_LEN + MAX_PERMITTED_DATA_INCREASE + 1;
+
+    // Fund the account up front, so only the size limit can refuse it
+    unsafe { *account.borrow_mut_lamports_unchecked() = RENT.minimum_balance(too_long) };
+
+    assert_eq!(
+        ProgramAccount::realloc_with_rent(&account, too_long, &payer, &RENT),
+        Err(ProgramError::InvalidRealloc)
+    );
+    assert_eq!(account.data_len(), ACCOUNT_LEN);
+}
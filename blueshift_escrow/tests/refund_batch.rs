@@ -0,0 +1,134 @@
+mod common;
+
+use blueshift_escrow::Escrow;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+const DEPOSIT: u64 = 1_000;
+
+/// One escrow's accounts: escrow, vault and the maker's mint_a account.
+struct Escrowed {
+    escrow: Pubkey,
+    vault: Pubkey,
+    maker_ata_a: Pubkey,
+}
+
+fn make_escrow(accounts: &mut Vec<(Pubkey, Account)>, maker: &Pubkey, seed: u64) -> Escrowed {
+    let mint_a = Pubkey::new_unique();
+    let mint_b = Pubkey::new_unique();
+    let (escrow, _) = escrow_address(maker, seed);
+    let vault = associated_token_address(&escrow, &mint_a, &TOKEN_PROGRAM_ID);
+    let maker_ata_a = associated_token_address(maker, &mint_a, &TOKEN_PROGRAM_ID);
+
+    accounts.push((mint_a, mint(6, &TOKEN_PROGRAM_ID)));
+    accounts.push((
+        escrow,
+        common::escrow(maker, seed, &mint_a, &mint_b, 500, DEPOSIT),
+    ));
+    accounts.push((
+        vault,
+        token_account(&mint_a, &escrow, DEPOSIT, &TOKEN_PROGRAM_ID),
+    ));
+    accounts.push((
+        maker_ata_a,
+        token_account(&mint_a, maker, 0, &TOKEN_PROGRAM_ID),
+    ));
+
+    Escrowed {
+        escrow,
+        vault,
+        maker_ata_a,
+    }
+}
+
+fn refund_batch(maker: &Pubkey, escrows: &[&Escrowed]) -> Instruction {
+    let mut metas = vec![
+        AccountMeta::new(*maker, true),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    for escrowed in escrows {
+        metas.push(AccountMeta::new(escrowed.escrow, false));
+        metas.push(AccountMeta::new(escrowed.vault, false));
+        metas.push(AccountMeta::new(escrowed.maker_ata_a, false));
+    }
+    Instruction::new_with_bytes(PROGRAM_ID, &[6], metas)
+}
+
+#[test]
+fn refund_batch_closes_every_escrow() {
+    let mollusk = mollusk();
+    let maker = Pubkey::new_unique();
+    let mut accounts = vec![
+        (maker, system_account(1_000_000_000)),
+        mollusk_svm_programs_token::token::keyed_account(),
+    ];
+    let escrows: Vec<Escrowed> = (1..=3)
+        .map(|seed| make_escrow(&mut accounts, &maker, seed))
+        .collect();
+
+    let instruction = refund_batch(&maker, &escrows.iter().collect::<Vec<_>>());
+    let result =
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+
+    for escrowed in &escrows {
+        assert_eq!(result.get_account(&escrowed.escrow).unwrap().lamports, 0);
+        assert_eq!(result.get_account(&escrowed.vault).unwrap().lamports, 0);
+        assert_eq!(
+            token_amount(result.get_account(&escrowed.maker_ata_a).unwrap()),
+            DEPOSIT
+        );
+    }
+
+    // The maker gets back the rent of all three escrows and vaults
+    let rent = 3 * (rent_exempt(Escrow::LEN) + rent_exempt(TOKEN_ACCOUNT_LEN));
+    assert_eq!(
+        result.get_account(&maker).unwrap().lamports,
+        1_000_000_000 + rent
+    );
+}
+
+#[test]
+fn refund_batch_skips_another_makers_escrow() {
+    let mollusk = mollusk();
+    let maker = Pubkey::new_unique();
+    let other_maker = Pubkey::new_unique();
+    let mut accounts = vec![
+        (maker, system_account(1_000_000_000)),
+        (other_maker, system_account(1_000_000_000)),
+        mollusk_svm_programs_token::token::keyed_account(),
+    ];
+    let first = make_escrow(&mut accounts, &maker, 1);
+    let foreign = make_escrow(&mut accounts, &other_maker, 1);
+    let last = make_escrow(&mut accounts, &maker, 2);
+
+    let foreign_before: Vec<Account> = [foreign.escrow, foreign.vault, foreign.maker_ata_a]
+        .iter()
+        .map(|key| accounts.iter().find(|(k, _)| k == key).unwrap().1.clone())
+        .collect();
+
+    let instruction = refund_batch(&maker, &[&first, &foreign, &last]);
+    let result =
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+
+    // The maker's own escrows on either side of it are still refunded
+    for escrowed in [&first, &last] {
+        assert_eq!(result.get_account(&escrowed.escrow).unwrap().lamports, 0);
+        assert_eq!(result.get_account(&escrowed.vault).unwrap().lamports, 0);
+        assert_eq!(
+            token_amount(result.get_account(&escrowed.maker_ata_a).unwrap()),
+            DEPOSIT
+        );
+    }
+
+    for (key, before) in [foreign.escrow, foreign.vault, foreign.maker_ata_a]
+        .iter()
+        .zip(&foreign_before)
+    {
+        assert_eq!(result.get_account(key).unwrap(), before);
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+/// Position of the vault in Take's account list.
+const VAULT: usize = 5;
+
+#[test]
+fn take_pays_mint_a_out_of_the_escrow_vault() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.take(None, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.taker_ata_a).unwrap()),
+        DEPOSIT
+    );
+    assert_eq!(
+        token_amount(result.get_account(&offer.maker_ata_b).unwrap()),
+        RECEIVE
+    );
+    assert_eq!(result.get_account(&offer.vault).unwrap().lamports, 0);
+    assert_eq!(result.get_account(&offer.escrow).unwrap().lamports, 0);
+}
+
+#[test]
+fn take_rejects_a_mint_a_source_other_than_the_vault() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let mut accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    // A mint_a account the taker controls, funded like the vault
+    let source = Pubkey::new_unique();
+    accounts.push((
+        source,
+        token_account(&offer.mint_a, &offer.taker, DEPOSIT, &TOKEN_PROGRAM_ID),
+    ));
+    let mut instruction = offer.take(None, &[]);
+    instruction.accounts[VAULT].pubkey = source;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
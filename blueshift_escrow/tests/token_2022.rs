@@ -0,0 +1,153 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const SEED: u64 = 7;
+const AMOUNT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+struct Accounts {
+    maker: Pubkey,
+    escrow: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    maker_ata_a: Pubkey,
+    vault: Pubkey,
+}
+
+fn setup(mint_a_account: Account) -> (Accounts, Vec<(Pubkey, Account)>) {
+    let maker = Pubkey::new_unique();
+    let mint_a = Pubkey::new_unique();
+    let mint_b = Pubkey::new_unique();
+    let (escrow, _) = escrow_address(&maker, SEED);
+    let maker_ata_a = associated_token_address(&maker, &mint_a, &TOKEN_2022_PROGRAM_ID);
+    let vault = associated_token_address(&escrow, &mint_a, &TOKEN_2022_PROGRAM_ID);
+
+    let accounts = vec![
+        (maker, system_account(1_000_000_000)),
+        (escrow, system_account(0)),
+        (mint_a, mint_a_account),
+        (mint_b, mint(6, &TOKEN_2022_PROGRAM_ID)),
+        (
+            maker_ata_a,
+            token_account(&mint_a, &maker, AMOUNT, &TOKEN_2022_PROGRAM_ID),
+        ),
+        (vault, system_account(0)),
+        mollusk_svm::program::keyed_account_for_system_program(),
+        mollusk_svm_programs_token::token2022::keyed_account(),
+        mollusk_svm_programs_token::associated_token::keyed_account(),
+    ];
+
+    (
+        Accounts {
+            maker,
+            escrow,
+            mint_a,
+            mint_b,
+            maker_ata_a,
+            vault,
+        },
+        accounts,
+    )
+}
+
+fn make(keys: &Accounts) -> Instruction {
+    let mut data = vec![0u8];
+    data.extend_from_slice(&SEED.to_le_bytes());
+    data.extend_from_slice(&RECEIVE.to_le_bytes());
+    data.extend_from_slice(&AMOUNT.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(keys.maker, true),
+            AccountMeta::new(keys.escrow, false),
+            AccountMeta::new_readonly(keys.mint_a, false),
+            AccountMeta::new_readonly(keys.mint_b, false),
+            AccountMeta::new(keys.maker_ata_a, false),
+            AccountMeta::new(keys.vault, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ],
+    )
+}
+
+fn refund(keys: &Accounts) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[2],
+        vec![
+            AccountMeta::new(keys.maker, true),
+            AccountMeta::new(keys.escrow, false),
+            AccountMeta::new_readonly(keys.mint_a, false),
+            AccountMeta::new(keys.vault, false),
+            AccountMeta::new(keys.maker_ata_a, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn make_and_refund_with_token_2022_mint() {
+    let mollusk = mollusk();
+    let (keys, accounts) = setup(mint(6, &TOKEN_2022_PROGRAM_ID));
+
+    let made =
+        mollusk.process_and_validate_instruction(&make(&keys), &accounts, &[Check::success()]);
+
+    let vault = made.get_account(&keys.vault).unwrap();
+    assert_eq!(vault.owner, TOKEN_2022_PROGRAM_ID);
+    assert_eq!(token_amount(vault), AMOUNT);
+    assert_eq!(
+        token_amount(made.get_account(&keys.maker_ata_a).unwrap()),
+        0
+    );
+    let escrow = made.get_account(&keys.escrow).unwrap();
+    assert_eq!(escrow.owner, PROGRAM_ID);
+    assert_eq!(escrow_of(escrow).mint_a, keys.mint_a.to_bytes());
+
+    let refunded = mollusk.process_and_validate_instruction(
+        &refund(&keys),
+        &made.resulting_accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(refunded.get_account(&keys.escrow).unwrap().lamports, 0);
+    assert_eq!(refunded.get_account(&keys.vault).unwrap().lamports, 0);
+    assert_eq!(
+        token_amount(refunded.get_account(&keys.maker_ata_a).unwrap()),
+        AMOUNT
+    );
+}
+
+#[test]
+fn make_rejects_token_2022_mint_with_extensions() {
+    let mollusk = mollusk();
+
+    // Base mint, padding up to the account-type byte, then one
+    // MintCloseAuthority entry
+    let mut mint_a = mint(6, &TOKEN_2022_PROGRAM_ID);
+    mint_a.data.resize(TOKEN_ACCOUNT_LEN, 0);
+    mint_a.data.push(1);
+    mint_a.data.extend_from_slice(&3u16.to_le_bytes());
+    mint_a.data.extend_from_slice(&32u16.to_le_bytes());
+    mint_a.data.extend_from_slice(&[0u8; 32]);
+    mint_a.lamports = rent_exempt(mint_a.data.len());
+    let (keys, accounts) = setup(mint_a);
+
+    mollusk.process_and_validate_instruction(
+        &make(&keys),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const DEPOSIT: u64 = 1_000;
+const RECEIVE: u64 = 500;
+
+#[test]
+fn maker_reprices_the_open_escrow() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    let result = mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.maker, 2 * RECEIVE),
+        &accounts,
+        &[Check::success()],
+    );
+    let escrow = escrow_of(result.get_account(&offer.escrow).unwrap());
+    let (receive, deposit) = (escrow.receive, escrow.deposit);
+    assert_eq!((receive, deposit), (2 * RECEIVE, DEPOSIT));
+}
+
+#[test]
+fn update_receive_rejects_a_signer_other_than_the_maker() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.taker, 2 * RECEIVE),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn update_receive_rejects_a_zero_receive() {
+    let mollusk = mollusk();
+    let offer = Offer::new();
+    let accounts = offer.made_accounts(RECEIVE, DEPOSIT, |_| {});
+
+    mollusk.process_and_validate_instruction(
+        &offer.update_receive(&offer.maker, 0),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
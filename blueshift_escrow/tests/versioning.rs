@@ -0,0 +1,39 @@
+//! `Escrow::load` and `load_mut` only accept the layout `init` stamps. These
+//! tests run natively over raw account data.
+
+use blueshift_escrow::{Escrow, EscrowError};
+use pinocchio::program_error::ProgramError;
+
+/// Escrow data as `make` writes it, with the version byte then set to
+/// `version`.
+fn escrow_data(version: u8) -> Vec<u8> {
+    let mut data = vec![0u8; Escrow::LEN];
+    Escrow::init(&mut data)
+        .unwrap()
+        .set_inner(7, [1; 32], [2; 32], [3; 32], 500, [255]);
+    data[0] = version;
+    data
+}
+
+#[test]
+fn current_version_escrow_loads() {
+    let mut data = escrow_data(Escrow::VERSION);
+
+    let seed = Escrow::load(&data).unwrap().seed;
+    assert_eq!(seed, 7);
+    assert!(Escrow::load_mut(&mut data).is_ok());
+}
+
+#[test]
+fn v0_escrow_is_rejected() {
+    let mut data = escrow_data(0);
+
+    assert_eq!(
+        Escrow::load(&data).err(),
+        Some(ProgramError::Custom(EscrowError::VersionMismatch as u32))
+    );
+    assert_eq!(
+        Escrow::load_mut(&mut data).err(),
+        Some(ProgramError::Custom(EscrowError::VersionMismatch as u32))
+    );
+}
@@ -0,0 +1,110 @@
+use pinocchio::program_error::ProgramError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AmmError {
+    /// The constant-product curve rejected the reserves or the trade.
+    CurveError = 1,
+    /// The swap output mint is not on the pool's allowlist.
+    OutputNotAllowed = 2,
+    /// The user's X token account cannot cover the deposit.
+    InsufficientX = 3,
+    /// The user's Y token account cannot cover the deposit.
+    InsufficientY = 4,
+    /// LP supply and vault reserves contradict each other.
+    PoolInconsistent = 6,
+    /// A pool mint is not an SPL Token mint, so its vault can't be moved.
+    UnsupportedMint = 7,
+    /// The swap would leave a vault below the pool's minimum reserve.
+    ReserveBelowFloor = 8,
+    /// The swap input exceeds the cap in force during the launch window.
+    LaunchProtection = 9,
+    /// A vault's balance moved by a different amount than the curve computed.
+    TransferMismatch = 10,
+    /// A batch holds more items than its caller-supplied limit.
+    TooManyItems = 11,
+    /// The pool's curve type is not one this program can price.
+    UnsupportedCurve = 12,
+    /// The config account has never been initialized as a pool.
+    PoolNotInitialized = 13,
+    /// The pool has a flash loan open and can't be traded against until it is repaid.
+    FlashLoanActive = 14,
+    /// The vault holds less than the flash loan plus its fee at repayment.
+    FlashLoanNotRepaid = 15,
+    /// The curve could not be built from the pool's reserves, supply and fee.
+    CurveInit = 16,
+    /// The curve rejected the swap for a reason other than slippage.
+    SwapFailed = 17,
+    /// The trade would settle outside the user's `min`/`max` bounds.
+    SlippageExceeded = 18,
+    /// The pool's state does not allow this instruction.
+    InvalidState = 19,
+    /// A vault account is not the pool's derived vault for its mint.
+    InvalidVault = 20,
+    /// The config account was written in a layout version this program can't read.
+    VersionMismatch = 21,
+    /// The sunset's grace period for LPs to exit has not elapsed yet.
+    SunsetPending = 22,
+    /// The account an initializer would create already exists.
+    AlreadyInitialized = 23,
+    /// The instruction can't run against a pool with a native SOL side.
+    NativeSolUnsupported = 24,
+    /// The swap's referral cut exceeds the pool's `max_referral_bps`.
+    ReferralTooHigh = 25,
+    /// The swap rounds to nothing on its input or output side.
+    ZeroTrade = 26,
+    /// The protocol and referral cuts add up to more than the swap's fee.
+    FeeSplitExceeded = 27,
+    /// The pool's spot price strays further from the oracle price than allowed.
+    OracleDivergence = 28,
+    /// The pool mints' decimals differ by more than the initializer allows.
+    DecimalsSpreadTooWide = 29,
+    /// A first deposit would mint no more LP than the locked minimum.
+    BelowMinimumLiquidity = 30,
+}
+
+impl From<AmmError> for ProgramError {
+    fn from(e: AmmError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+/// Decodes a `ProgramError::Custom` code back into its `AmmError` variant.
+#[cfg(feature = "client")]
+impl TryFrom<u32> for AmmError {
+    type Error = ProgramError;
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(AmmError::CurveError),
+            2 => Ok(AmmError::OutputNotAllowed),
+            3 => Ok(AmmError::InsufficientX),
+            4 => Ok(AmmError::InsufficientY),
+            6 => Ok(AmmError::PoolInconsistent),
+            7 => Ok(AmmError::UnsupportedMint),
+            8 => Ok(AmmError::ReserveBelowFloor),
+            9 => Ok(AmmError::LaunchProtection),
+            10 => Ok(AmmError::TransferMismatch),
+            11 => Ok(AmmError::TooManyItems),
+            12 => Ok(AmmError::UnsupportedCurve),
+            13 => Ok(AmmError::PoolNotInitialized),
+            14 => Ok(AmmError::FlashLoanActive),
+            15 => Ok(AmmError::FlashLoanNotRepaid),
+            16 => Ok(AmmError::CurveInit),
+            17 => Ok(AmmError::SwapFailed),
+            18 => Ok(AmmError::SlippageExceeded),
+            19 => Ok(AmmError::InvalidState),
+            20 => Ok(AmmError::InvalidVault),
+            21 => Ok(AmmError::VersionMismatch),
+            22 => Ok(AmmError::SunsetPending),
+            23 => Ok(AmmError::AlreadyInitialized),
+            24 => Ok(AmmError::NativeSolUnsupported),
+            25 => Ok(AmmError::ReferralTooHigh),
+            26 => Ok(AmmError::ZeroTrade),
+            27 => Ok(AmmError::FeeSplitExceeded),
+            28 => Ok(AmmError::OracleDivergence),
+            29 => Ok(AmmError::DecimalsSpreadTooWide),
+            30 => Ok(AmmError::BelowMinimumLiquidity),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
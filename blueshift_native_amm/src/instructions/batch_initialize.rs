@@ -0,0 +1,112 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    errors::AmmError,
+    instructions::{Initialize, InitializeAccounts, InitializeInstructionData},
+};
+
+use super::helpers::*;
+
+/// Accounts each pool in the batch takes, in `Initialize` order. The LP
+/// ATAs a pre-seeded pool needs are not among them, so batched pools must
+/// start with empty vaults.
+const INITIALIZE_ACCOUNTS_LEN: usize = 9;
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+/// A caller-chosen item cap followed by one `InitializeInstructionData` per
+/// pool, in its full v2 layout for a v1 batch and its full v3 layout from v2
+/// on. The caller sizes `max_items` to the compute budget they request.
+pub struct BatchInitializeInstructionData<'a> {
+    pub max_items: u8,
+    /// The `InitializeInstructionData` version each pool is packed in.
+    pub pool_version: u8,
+    pub pools: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for BatchInitializeInstructionData<'a> {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (version, data) = split_version(data, INSTRUCTION_DATA_V2)?;
+        let Some((&max_items, pools)) = data.split_first() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let pool_version = match version {
+            INSTRUCTION_DATA_V1 => INSTRUCTION_DATA_V2,
+            _ => INSTRUCTION_DATA_V3,
+        };
+        let pool_len = pool_data_len(pool_version);
+        let count = pools.len() / pool_len;
+        if pools.len() % pool_len != 0 || count == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // Refuse up front rather than run out of compute mid-batch
+        if count > max_items as usize {
+            return Err(AmmError::TooManyItems.into());
+        }
+        Ok(Self {
+            max_items,
+            pool_version,
+            pools,
+        })
+    }
+}
+
+/// Length of one pool's data in the full `version` layout, which only v3
+/// extends past v2.
+const fn pool_data_len(version: u8) -> usize {
+    match version {
+        INSTRUCTION_DATA_V3 => size_of::<InitializeInstructionData>(),
+        _ => size_of::<InitializeInstructionData>() - size_of::<u8>(),
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Runs `Initialize` for several pools, each with its own group of accounts
+/// taken in order from the accounts slice. Any failing pool fails the whole
+/// transaction, so no pool is left created without the rest.
+pub struct BatchInitialize<'a> {
+    pub accounts: &'a [AccountInfo],
+    pub instruction_data: BatchInitializeInstructionData<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BatchInitialize<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = BatchInitializeInstructionData::try_from(data)?;
+        let pools = instruction_data.pools.len() / pool_data_len(instruction_data.pool_version);
+        if accounts.len() < pools * INITIALIZE_ACCOUNTS_LEN {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> BatchInitialize<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let pool_version = self.instruction_data.pool_version;
+        let pools = self
+            .instruction_data
+            .pools
+            .chunks_exact(pool_data_len(pool_version));
+        let groups = self.accounts.chunks_exact(INITIALIZE_ACCOUNTS_LEN);
+
+        for (data, accounts) in pools.zip(groups) {
+            Initialize {
+                accounts: InitializeAccounts::try_from(accounts)?,
+                instruction_data: InitializeInstructionData::parse(pool_version, data)?,
+            }
+            .process()?;
+        }
+
+        Ok(())
+    }
+}
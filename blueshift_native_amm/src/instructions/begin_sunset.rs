@@ -0,0 +1,101 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    errors::AmmError,
+    state::{AmmState, Config},
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct BeginSunsetAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for BeginSunsetAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct BeginSunsetInstructionData {
+    pub sunset_grace: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for BeginSunsetInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.sunset_grace < 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// First half of retiring a pool: disables it with a `sunset_grace`-second
+/// grace period, during which it behaves as `WithdrawOnly` so LPs can exit.
+/// `FinalizeSunset` pins it `Disabled` once the window has elapsed.
+pub struct BeginSunset<'a> {
+    pub accounts: BeginSunsetAccounts<'a>,
+    pub instruction_data: BeginSunsetInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BeginSunset<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = BeginSunsetAccounts::try_from(accounts)?;
+        let instruction_data = BeginSunsetInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> BeginSunset<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &30;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can sunset, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        // Only a live pool has anything to wind down
+        if config.state() != AmmState::Initialized as u8 {
+            return Err(AmmError::InvalidState.into());
+        }
+
+        let clock = Clock::get()?;
+        config.disable(clock.unix_timestamp, self.instruction_data.sunset_grace)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{create_program_address, find_program_address},
+    ProgramResult,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::state::{AmmState, Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct CloseConfigAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// The config PDA's LP ATA holding the locked `MINIMUM_LIQUIDITY`.
+    pub locked_lp_ata: &'a AccountInfo,
+    /// X and Y token accounts receiving whatever the vaults still hold.
+    pub destination_x: &'a AccountInfo,
+    pub destination_y: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseConfigAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, mint_lp, vault_x, vault_y, destination, token_program, locked_lp_ata, destination_x, destination_y, ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self {
+            authority,
+            config,
+            mint_lp,
+            vault_x,
+            vault_y,
+            destination,
+            token_program,
+            locked_lp_ata,
+            destination_x,
+            destination_y,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Closes a retired pool's config, sending its rent to `destination`. The
+/// pool must be `WithdrawOnly` or `Disabled` with no LP outstanding beyond
+/// the locked `MINIMUM_LIQUIDITY`. That LP is burned and the reserve dust
+/// it still backs is swept to `destination_x`/`destination_y`, so nothing is
+/// left that the config would need to sign for.
+pub struct CloseConfig<'a> {
+    pub accounts: CloseConfigAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseConfig<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CloseConfigAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CloseConfig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can close, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        // FinalizeSunset leaves pools Disabled, an incident WithdrawOnly
+        if config.state() != AmmState::WithdrawOnly as u8
+            && config.state() != AmmState::Disabled as u8
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Check mint_lp and locked LP ATA derivations
+        let mint_lp = create_program_address(
+            &[b"mint_lp", self.accounts.config.key(), &config.lp_bump()],
+            &crate::ID,
+        )?;
+        if mint_lp.ne(self.accounts.mint_lp.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (locked_lp_ata, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                LpMint::token_program(self.accounts.mint_lp)?,
+                self.accounts.mint_lp.key(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if locked_lp_ata.ne(self.accounts.locked_lp_ata.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The locked minimum is the only LP allowed to remain; a pool that
+        // never took a deposit has no locked ATA at all
+        let locked = match self.accounts.locked_lp_ata.data_is_empty() {
+            true => 0,
+            false => unsafe { LpMint::account_unchecked(self.accounts.locked_lp_ata)? }.amount(),
+        };
+        if unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? }.supply() != locked {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Check vault derivations
+        for (vault, mint) in [
+            (self.accounts.vault_x, config.mint_x()),
+            (self.accounts.vault_y, config.mint_y()),
+        ] {
+            let (expected, _) = find_program_address(
+                &[
+                    self.accounts.config.key(),
+                    self.accounts.token_program.key(),
+                    mint,
+                ],
+                &pinocchio_associated_token_account::ID,
+            );
+            if expected.ne(vault.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        let seed_binding = config.seed().to_le_bytes();
+        let config_bump = config.config_bump();
+        let config_seeds = [
+            Seed::from(b"config"),
+            Seed::from(&seed_binding),
+            Seed::from(config.mint_x().as_ref()),
+            Seed::from(config.mint_y().as_ref()),
+            Seed::from(&config_bump),
+        ];
+        let signer = Signer::from(&config_seeds);
+
+        // Burn the locked LP, which the config alone can sign for
+        if locked != 0 {
+            LpMint::burn(
+                self.accounts.locked_lp_ata,
+                self.accounts.mint_lp,
+                self.accounts.config,
+                locked,
+                &[signer.clone()],
+            )?;
+        }
+
+        // Sweep the dust the locked LP backed; a vault never created holds
+        // nothing
+        for (vault, destination) in [
+            (self.accounts.vault_x, self.accounts.destination_x),
+            (self.accounts.vault_y, self.accounts.destination_y),
+        ] {
+            if vault.data_is_empty() {
+                continue;
+            }
+            let amount = unsafe { TokenAccount::from_account_info_unchecked(vault)? }.amount();
+            if amount != 0 {
+                Transfer {
+                    from: vault,
+                    to: destination,
+                    authority: self.accounts.config,
+                    amount,
+                }
+                .invoke_signed(&[signer.clone()])?;
+            }
+        }
+
+        ProgramAccount::close(self.accounts.config, self.accounts.destination)
+    }
+}
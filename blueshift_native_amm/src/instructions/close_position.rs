@@ -0,0 +1,58 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::state::Position;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct ClosePositionAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClosePositionAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, position, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !user.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        ProgramAccount::check(position)?;
+        Ok(Self { user, position })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Closes a fully exited `Position`, returning its rent to the user.
+pub struct ClosePosition<'a> {
+    pub accounts: ClosePositionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ClosePosition<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ClosePositionAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ClosePosition<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let position = unsafe { Position::load(self.accounts.position)? };
+
+        if position.owner() != self.accounts.user.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if position.lp_amount() != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        ProgramAccount::close(self.accounts.position, self.accounts.user)
+    }
+}
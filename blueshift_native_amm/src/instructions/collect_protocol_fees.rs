@@ -0,0 +1,153 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::state::Config;
+
+/// PDA owning the pool's protocol fee vaults (its ATAs for `mint_x`/`mint_y`),
+/// kept apart from the config so fees never mix with reserves.
+#[inline(always)]
+pub fn find_fee_authority(config: &Pubkey) -> (Pubkey, u8) {
+    find_program_address(&[b"fees", config], &crate::ID)
+}
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct CollectProtocolFeesAccounts<'a> {
+    pub fee_authority: &'a AccountInfo,
+    pub fee_vault: &'a AccountInfo,
+    pub recipient_ata: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CollectProtocolFeesAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [fee_authority, fee_vault, recipient_ata, config, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            fee_authority,
+            fee_vault,
+            recipient_ata,
+            config,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct CollectProtocolFeesInstructionData {
+    pub is_x: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CollectProtocolFeesInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Permissionless crank: moves the whole X or Y fee vault balance to the
+/// config's `protocol_fee_recipient`. Anyone may call it, but funds can only
+/// ever reach the recipient's ATA.
+pub struct CollectProtocolFees<'a> {
+    pub accounts: CollectProtocolFeesAccounts<'a>,
+    pub instruction_data: CollectProtocolFeesInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CollectProtocolFees<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = CollectProtocolFeesAccounts::try_from(accounts)?;
+        let instruction_data = CollectProtocolFeesInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> CollectProtocolFees<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+        config.check_initialized()?;
+
+        let recipient = config.protocol_fee_recipient();
+        if recipient.iter().all(|&b| b == 0) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mint = match self.instruction_data.is_x != 0 {
+            true => config.mint_x(),
+            false => config.mint_y(),
+        };
+
+        // Check fee authority and fee vault derivations
+        let (fee_authority, fee_authority_bump) = find_fee_authority(self.accounts.config.key());
+        if fee_authority.ne(self.accounts.fee_authority.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (fee_vault, _) = find_program_address(
+            &[&fee_authority, self.accounts.token_program.key(), mint],
+            &pinocchio_associated_token_account::ID,
+        );
+        if fee_vault.ne(self.accounts.fee_vault.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Funds only ever go to the configured recipient
+        let (recipient_ata, _) = find_program_address(
+            &[recipient, self.accounts.token_program.key(), mint],
+            &pinocchio_associated_token_account::ID,
+        );
+        if recipient_ata.ne(self.accounts.recipient_ata.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let amount =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.fee_vault)? }.amount();
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let fee_authority_bump = [fee_authority_bump];
+        let fee_authority_seeds = [
+            Seed::from(b"fees"),
+            Seed::from(self.accounts.config.key().as_ref()),
+            Seed::from(&fee_authority_bump),
+        ];
+        let signer = Signer::from(&fee_authority_seeds);
+
+        Transfer {
+            from: self.accounts.fee_vault,
+            to: self.accounts.recipient_ata,
+            authority: self.accounts.fee_authority,
+            amount,
+        }
+        .invoke_signed(&[signer])?;
+
+        Ok(())
+    }
+}
@@ -1,21 +1,21 @@
-use core::mem::size_of;
+use core::mem::{size_of, MaybeUninit};
 
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{create_program_address, find_program_address, Pubkey},
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::{
-    instructions::{MintTo, Transfer},
-    state::{Mint, TokenAccount},
-};
+use pinocchio_token::state::TokenAccount;
 
-use constant_product_curve::ConstantProduct;
+use crate::{
+    errors::AmmError,
+    state::{AmmState, Config, Position},
+};
 
-use crate::state::{AmmState, Config};
+use super::helpers::*;
 
 // ─── Accounts ───────────────────────────────────────────────────────────────
 
@@ -29,12 +29,30 @@ pub struct DepositAccounts<'a> {
     pub user_lp_ata: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    /// The user's `Position`, updated when supplied.
+    pub position: Option<&'a AccountInfo>,
+    /// Trailing accounts, found by address. The config PDA's LP ATA is only
+    /// needed on the first deposit, where it receives the locked
+    /// `MINIMUM_LIQUIDITY`.
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> DepositAccounts<'a> {
+    /// The trailing account at `key`, for accounts the deposit can derive.
+    #[inline(always)]
+    fn trailing(&self, key: &Pubkey) -> Result<&'a AccountInfo, ProgramError> {
+        self.remaining
+            .iter()
+            .find(|account| account.key() == key)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, mint_lp, vault_x, vault_y, user_x_ata, user_y_ata, user_lp_ata, config, token_program, ..] =
+        let [user, mint_lp, vault_x, vault_y, user_x_ata, user_y_ata, user_lp_ata, config, token_program, system_program, remaining @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -49,10 +67,22 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             user_lp_ata,
             config,
             token_program,
+            system_program,
+            // The only trailing account the program owns; its address is
+            // checked against the derivation before it is written
+            position: remaining
+                .iter()
+                .find(|account| account.is_owned_by(&crate::ID)),
+            remaining,
         })
     }
 }
 
+/// LP permanently locked in the config PDA's ATA on the first deposit, so the
+/// supply can never be drained back to a few units and a first depositor
+/// can't inflate the price per share.
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+
 // ─── Instruction Data ───────────────────────────────────────────────────────
 
 #[repr(C, packed)]
@@ -60,29 +90,49 @@ pub struct DepositInstructionData {
     pub amount: u64,
     pub max_x: u64,
     pub max_y: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
     pub expiration: i64,
+    /// Least LP the user must be minted, after the locked minimum; zero sets
+    /// no bound.
+    pub min_lp_out: u64,
 }
 
 impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
     type Error = ProgramError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<Self>() {
+        const DEPOSIT_DATA_LEN_V2: usize = size_of::<DepositInstructionData>();
+        const DEPOSIT_DATA_LEN_V1: usize = DEPOSIT_DATA_LEN_V2 - size_of::<u64>();
+
+        // v2 appends the LP output bound; v1 leaves it zeroed, which disables it
+        let (version, data) = split_version(data, INSTRUCTION_DATA_V2)?;
+        let len = match version {
+            INSTRUCTION_DATA_V1 => DEPOSIT_DATA_LEN_V1,
+            _ => DEPOSIT_DATA_LEN_V2,
+        };
+        if data.len() != len {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        let result = {
+            let mut raw: MaybeUninit<[u8; DEPOSIT_DATA_LEN_V2]> = MaybeUninit::uninit();
+            let raw_ptr = raw.as_mut_ptr() as *mut u8;
+            unsafe {
+                core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, len);
+                core::ptr::write_bytes(raw_ptr.add(len), 0, DEPOSIT_DATA_LEN_V2 - len);
+                (raw.as_ptr() as *const Self).read_unaligned()
+            }
+        };
         if result.amount == 0 || result.max_x == 0 || result.max_y == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let clock = Clock::get()?;
-        if clock.unix_timestamp > result.expiration {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        check_expiration(result.expiration)?;
         Ok(result)
     }
 }
 
 // ─── Instruction ────────────────────────────────────────────────────────────
 
+/// A wrapped SOL side is paid straight from the user's lamports and wrapped
+/// into the vault, leaving `user_x_ata`/`user_y_ata` for that side unused.
 pub struct Deposit<'a> {
     pub accounts: DepositAccounts<'a>,
     pub instruction_data: DepositInstructionData,
@@ -93,6 +143,20 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
     fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = DepositAccounts::try_from(accounts)?;
         let instruction_data = DepositInstructionData::try_from(data)?;
+
+        // Create the user's LP ATA if needed. Soulbound LP lives under
+        // Token-2022, where the user must create the ATA beforehand
+        if accounts.mint_lp.is_owned_by(accounts.token_program.key()) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.user_lp_ata,
+                accounts.mint_lp,
+                accounts.user,
+                accounts.user,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
         Ok(Self {
             accounts,
             instruction_data,
@@ -104,11 +168,19 @@ impl<'a> Deposit<'a> {
     pub const DISCRIMINATOR: &'a u8 = &1;
 
     pub fn process(&mut self) -> ProgramResult {
-        let config = unsafe { Config::load(self.accounts.config)? };
+        // The config signs for the vaults, so it can never act as the user
+        if self.accounts.user.key() == self.accounts.config.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+        config.check_no_flash_loan()?;
+        config.check_curve()?;
 
         // Validate AMM state
         if config.state() != AmmState::Initialized as u8 {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(AmmError::InvalidState.into());
         }
 
         // Check vault derivations
@@ -121,7 +193,7 @@ impl<'a> Deposit<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_x.ne(self.accounts.vault_x.key()) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(AmmError::InvalidVault.into());
         }
 
         let (vault_y, _) = find_program_address(
@@ -133,59 +205,116 @@ impl<'a> Deposit<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(AmmError::InvalidVault.into());
+        }
+
+        // Check mint_lp derivation
+        let mint_lp = create_program_address(
+            &[b"mint_lp", self.accounts.config.key(), &config.lp_bump()],
+            &crate::ID,
+        )?;
+        if mint_lp.ne(self.accounts.mint_lp.key()) {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // Deserialize token accounts
-        let mint_lp = unsafe { Mint::from_account_info_unchecked(self.accounts.mint_lp)? };
+        // Deserialize token accounts; the LP mint may belong to SPL Token,
+        // or Token-2022 for soulbound pools
+        let mint_lp = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? };
         let vault_x_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
         let vault_y_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
 
+        // Reject pools left in an impossible state
+        validate_reserves(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Some(mint_lp.supply()),
+        )?;
+
+        // Credit the time since the last update to the pre-trade price
+        config.update_price_cumulatives(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Clock::get()?.unix_timestamp,
+        );
+
         // Grab the amounts to deposit
-        let (x, y) = match mint_lp.supply() == 0
-            && vault_x_account.amount() == 0
-            && vault_y_account.amount() == 0
-        {
-            true => (self.instruction_data.max_x, self.instruction_data.max_y),
-            false => {
-                let amounts = ConstantProduct::xy_deposit_amounts_from_l(
-                    vault_x_account.amount(),
-                    vault_y_account.amount(),
-                    mint_lp.supply(),
-                    self.instruction_data.amount,
-                    6,
-                )
-                .map_err(|_| ProgramError::InvalidArgument)?;
-                (amounts.x, amounts.y)
-            }
+        let first_deposit = mint_lp.supply() == 0;
+        let (x, y) = match deposit_amounts(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            mint_lp.supply(),
+            self.instruction_data.amount,
+        )? {
+            Some(amounts) => amounts,
+            None => (self.instruction_data.max_x, self.instruction_data.max_y),
         };
 
         // Check for slippage
         if !(x <= self.instruction_data.max_x && y <= self.instruction_data.max_y) {
-            return Err(ProgramError::InvalidArgument);
+            return Err(AmmError::SlippageExceeded.into());
         }
 
-        // Transfer X tokens from user to vault
-        Transfer {
-            from: self.accounts.user_x_ata,
-            to: self.accounts.vault_x,
-            authority: self.accounts.user,
-            amount: x,
+        // Vault balances and LP supply are u64 on-chain, so a deposit that
+        // would push any of them past u64::MAX fails cleanly here rather than
+        // in the token program
+        vault_x_account
+            .amount()
+            .checked_add(x)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        vault_y_account
+            .amount()
+            .checked_add(y)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        mint_lp
+            .supply()
+            .checked_add(self.instruction_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Fail early with an error naming the short side. A native SOL side
+        // is paid from the user's lamports rather than their token account
+        let user_x_balance = match config.is_native_x() {
+            true => self.accounts.user.lamports(),
+            false => {
+                unsafe { TokenAccount::from_account_info_unchecked(self.accounts.user_x_ata)? }
+                    .amount()
+            }
+        };
+        if user_x_balance < x {
+            return Err(AmmError::InsufficientX.into());
+        }
+        let user_y_balance = match config.is_native_y() {
+            true => self.accounts.user.lamports(),
+            false => {
+                unsafe { TokenAccount::from_account_info_unchecked(self.accounts.user_y_ata)? }
+                    .amount()
+            }
+        };
+        if user_y_balance < y {
+            return Err(AmmError::InsufficientY.into());
         }
-        .invoke()?;
+
+        // Transfer X tokens from user to vault
+        NativeSol::transfer_in(
+            config.is_native_x(),
+            self.accounts.user,
+            self.accounts.user_x_ata,
+            self.accounts.vault_x,
+            x,
+        )?;
 
         // Transfer Y tokens from user to vault
-        Transfer {
-            from: self.accounts.user_y_ata,
-            to: self.accounts.vault_y,
-            authority: self.accounts.user,
-            amount: y,
-        }
-        .invoke()?;
+        NativeSol::transfer_in(
+            config.is_native_y(),
+            self.accounts.user,
+            self.accounts.user_y_ata,
+            self.accounts.vault_y,
+            y,
+        )?;
 
         // Mint LP tokens to user
+        let amount = self.instruction_data.amount;
         let seed_binding = config.seed().to_le_bytes();
         let config_bump = config.config_bump();
         let config_seeds = [
@@ -197,14 +326,136 @@ impl<'a> Deposit<'a> {
         ];
         let signer = Signer::from(&config_seeds);
 
-        MintTo {
-            mint: self.accounts.mint_lp,
-            account: self.accounts.user_lp_ata,
-            mint_authority: self.accounts.config,
-            amount: self.instruction_data.amount,
+        // The first depositor funds the locked minimum out of their own LP
+        let user_lp = match first_deposit {
+            true => {
+                if amount <= MINIMUM_LIQUIDITY {
+                    return Err(AmmError::BelowMinimumLiquidity.into());
+                }
+                self.lock_minimum_liquidity(signer.clone())?;
+                amount - MINIMUM_LIQUIDITY
+            }
+            false => amount,
+        };
+
+        // The first depositor is minted `amount` less the locked minimum, so
+        // bound what the user ends up with
+        if user_lp < self.instruction_data.min_lp_out {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        // Reject dust positions below the pool's LP floor, on the same
+        // credited amount
+        if user_lp < config.min_lp_mint() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        LpMint::mint_to(
+            self.accounts.mint_lp,
+            self.accounts.user_lp_ata,
+            self.accounts.config,
+            user_lp,
+            &[signer],
+        )?;
+
+        // Keep the user's position in sync
+        if let Some(position_account) = self.accounts.position {
+            let position = unsafe {
+                Position::load_for(
+                    position_account,
+                    self.accounts.user.key(),
+                    self.accounts.config.key(),
+                )?
+            };
+            let expected = create_program_address(
+                &[
+                    b"position",
+                    self.accounts.config.key(),
+                    self.accounts.user.key(),
+                    &position.bump(),
+                ],
+                &crate::ID,
+            )?;
+            if expected.ne(position_account.key()) {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            position.set_lp_amount(
+                position
+                    .lp_amount()
+                    .checked_add(user_lp)
+                    .ok_or(ProgramError::ArithmeticOverflow)?,
+            );
         }
-        .invoke_signed(&[signer])?;
 
         Ok(())
     }
+
+    /// Mints `MINIMUM_LIQUIDITY` to the config PDA's own LP ATA. Nothing can
+    /// move it out since the program never signs an LP transfer or burn as
+    /// the config.
+    fn lock_minimum_liquidity(&self, signer: Signer) -> ProgramResult {
+        let (locked_lp_ata, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                LpMint::token_program(self.accounts.mint_lp)?,
+                self.accounts.mint_lp.key(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        let locked_lp_ata = self.accounts.trailing(&locked_lp_ata)?;
+
+        // Soulbound pools need the ATA created beforehand, as for the user
+        if self
+            .accounts
+            .mint_lp
+            .is_owned_by(self.accounts.token_program.key())
+        {
+            AssociatedTokenAccount::init_if_needed(
+                locked_lp_ata,
+                self.accounts.mint_lp,
+                self.accounts.user,
+                self.accounts.config,
+                self.accounts.system_program,
+                self.accounts.token_program,
+            )?;
+        }
+
+        LpMint::mint_to(
+            self.accounts.mint_lp,
+            locked_lp_ata,
+            self.accounts.config,
+            MINIMUM_LIQUIDITY,
+            &[signer],
+        )
+    }
+}
+
+/// X/Y required to mint `amount` LP at the given reserves, or `None` for an
+/// empty pool where the first depositor sets the price.
+///
+/// Each side is `reserve * amount / supply` rounded up on its own, so both
+/// sides overpay by less than one unit and neither is favored over many
+/// deposits. This keeps rounding against the user everywhere: withdraws
+/// round down and exact-output swaps round their input up. Amounts that
+/// don't fit a `u64` fail with `ArithmeticOverflow` instead of wrapping.
+///
+/// Tokens sitting in a vault before any LP exists (e.g. a donation ahead of
+/// the first deposit) have no LP against them either, so such a pool is
+/// still empty: the first depositor sets the price and takes the donation
+/// along with their own deposit, rather than the pool being stuck.
+#[inline(always)]
+pub fn deposit_amounts(
+    x: u64,
+    y: u64,
+    supply: u64,
+    amount: u64,
+) -> Result<Option<(u64, u64)>, ProgramError> {
+    if supply == 0 {
+        return Ok(None);
+    }
+    let share = |reserve: u64| -> Result<u64, ProgramError> {
+        let amount = (reserve as u128 * amount as u128).div_ceil(supply as u128);
+        u64::try_from(amount).map_err(|_| ProgramError::ArithmeticOverflow)
+    };
+    Ok(Some((share(x)?, share(y)?)))
 }
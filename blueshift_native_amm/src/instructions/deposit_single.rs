@@ -0,0 +1,240 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    errors::AmmError,
+    instructions::{
+        Deposit, DepositAccounts, DepositInstructionData, Swap, SwapAccounts, SwapInstructionData,
+    },
+    state::Config,
+};
+
+use super::helpers::*;
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct DepositSingleInstructionData {
+    pub is_x: u8,
+    pub amount: u64,
+    pub min_lp_out: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DepositSingleInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 || result.min_lp_out == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        check_expiration(result.expiration)?;
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Deposits `amount` of one side only: swaps the share of it that balances
+/// the remainder against the post-swap reserves, then deposits both sides.
+/// Takes the same accounts as `Deposit`, and the swap leg runs as a regular
+/// `Swap`. Pools with an output allowlist are not supported, and an empty
+/// pool has no price to balance against.
+///
+/// Pools with a native SOL side are rejected: the swap leg would unwrap its
+/// output by closing the user's ATA, and Deposit pulls a native side from
+/// lamports rather than from that ATA.
+pub struct DepositSingle<'a> {
+    pub accounts: DepositAccounts<'a>,
+    pub instruction_data: DepositSingleInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DepositSingle<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DepositAccounts::try_from(accounts)?;
+        let instruction_data = DepositSingleInstructionData::try_from(data)?;
+
+        // Create the user's LP ATA if needed, as Deposit does
+        if accounts.mint_lp.is_owned_by(accounts.token_program.key()) {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.user_lp_ata,
+                accounts.mint_lp,
+                accounts.user,
+                accounts.user,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> DepositSingle<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let is_x = self.instruction_data.is_x != 0;
+        let amount = self.instruction_data.amount;
+
+        let (input_vault, output_vault, user_out_ata) = match is_x {
+            true => (
+                self.accounts.vault_x,
+                self.accounts.vault_y,
+                self.accounts.user_y_ata,
+            ),
+            false => (
+                self.accounts.vault_y,
+                self.accounts.vault_x,
+                self.accounts.user_x_ata,
+            ),
+        };
+
+        // Size the swap leg. Swap and Deposit verify every account, so these
+        // reads only steer the split
+        let fee = {
+            let config = unsafe { Config::load(self.accounts.config)? };
+            config.check_initialized()?;
+            if config.is_native_x() || config.is_native_y() {
+                return Err(AmmError::NativeSolUnsupported.into());
+            }
+            config.fee()
+        };
+        let supply = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? }.supply();
+        if supply == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let reserve_in =
+            unsafe { TokenAccount::from_account_info_unchecked(input_vault)? }.amount();
+        let swap_amount = single_sided_swap_amount(reserve_in, amount, fee);
+        if swap_amount == 0 || swap_amount >= amount {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        let out_before =
+            unsafe { TokenAccount::from_account_info_unchecked(user_out_ata)? }.amount();
+        let lp_before =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.user_lp_ata)? }
+                .amount();
+
+        Swap {
+            accounts: SwapAccounts {
+                user: self.accounts.user,
+                mint_lp: self.accounts.mint_lp,
+                user_x_ata: self.accounts.user_x_ata,
+                user_y_ata: self.accounts.user_y_ata,
+                vault_x: self.accounts.vault_x,
+                vault_y: self.accounts.vault_y,
+                config: self.accounts.config,
+                token_program: self.accounts.token_program,
+                remaining: self.accounts.remaining,
+            },
+            instruction_data: SwapInstructionData {
+                is_x: self.instruction_data.is_x,
+                amount: swap_amount,
+                min: 1,
+                expiration: self.instruction_data.expiration,
+                oracle_price: 0,
+                max_divergence_bps: 0,
+                referral_bps: 0,
+                is_exact_in: 1,
+                expiration_slot: 0,
+            },
+        }
+        .process()?;
+
+        // Deposit as much LP as both leftovers cover at the new reserves
+        let available_in = amount - swap_amount;
+        let available_out = unsafe { TokenAccount::from_account_info_unchecked(user_out_ata)? }
+            .amount()
+            .saturating_sub(out_before);
+        let reserve_in =
+            unsafe { TokenAccount::from_account_info_unchecked(input_vault)? }.amount();
+        let reserve_out =
+            unsafe { TokenAccount::from_account_info_unchecked(output_vault)? }.amount();
+        let lp = core::cmp::min(
+            available_in as u128 * supply as u128 / reserve_in as u128,
+            available_out as u128 * supply as u128 / reserve_out as u128,
+        ) as u64;
+        if lp == 0 {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        let (max_x, max_y) = match is_x {
+            true => (available_in, available_out),
+            false => (available_out, available_in),
+        };
+        Deposit {
+            accounts: DepositAccounts {
+                user: self.accounts.user,
+                mint_lp: self.accounts.mint_lp,
+                vault_x: self.accounts.vault_x,
+                vault_y: self.accounts.vault_y,
+                user_x_ata: self.accounts.user_x_ata,
+                user_y_ata: self.accounts.user_y_ata,
+                user_lp_ata: self.accounts.user_lp_ata,
+                config: self.accounts.config,
+                token_program: self.accounts.token_program,
+                system_program: self.accounts.system_program,
+                position: self.accounts.position,
+                remaining: self.accounts.remaining,
+            },
+            instruction_data: DepositInstructionData {
+                amount: lp,
+                max_x,
+                max_y,
+                expiration: self.instruction_data.expiration,
+                // Bounded below on the LP actually received instead
+                min_lp_out: 0,
+            },
+        }
+        .process()?;
+
+        // Check for slippage on the LP actually received
+        let lp_after =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.user_lp_ata)? }
+                .amount();
+        if lp_after.saturating_sub(lp_before) < self.instruction_data.min_lp_out {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Share of `amount` to swap into the other side so the remainder and the
+/// swap output match the post-swap reserve ratio, for a pool holding
+/// `reserve` of the input token at `fee` basis points:
+///
+/// `s = (sqrt(r²(2 - f)² + 4(1 - f)·a·r) - r(2 - f)) / 2(1 - f)`
+///
+/// Inputs are scaled down to 40 bits first so the squares fit in a `u128`,
+/// which costs at most a few units of precision on very large amounts.
+#[inline(always)]
+pub fn single_sided_swap_amount(reserve: u64, amount: u64, fee: u16) -> u64 {
+    let shift = (64 - reserve.max(amount).leading_zeros()).saturating_sub(40);
+    let r = (reserve >> shift) as u128;
+    let a = (amount >> shift) as u128;
+
+    // (1 - f) and (2 - f), both scaled by 10_000
+    let g = 10_000u128.saturating_sub(fee as u128);
+    if g == 0 {
+        return 0;
+    }
+    let h = 10_000 + g;
+
+    let root = isqrt(r * r * h * h + 4 * g * 10_000 * a * r);
+    let scaled = root.saturating_sub(r * h) / (2 * g);
+    ((scaled as u64) << shift).min(amount)
+}
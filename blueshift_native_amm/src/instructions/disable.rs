@@ -0,0 +1,96 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::{AmmState, Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct DisableAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DisableAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct DisableInstructionData {
+    pub grace_seconds: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DisableInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.grace_seconds < 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Disables the pool. Withdraws stay open for `grace_seconds` so LPs can exit
+/// before the pool becomes fully `Disabled`.
+pub struct Disable<'a> {
+    pub accounts: DisableAccounts<'a>,
+    pub instruction_data: DisableInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Disable<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DisableAccounts::try_from(accounts)?;
+        let instruction_data = DisableInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Disable<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can disable, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        if config.state() == AmmState::Disabled as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let clock = Clock::get()?;
+        config.disable(clock.unix_timestamp, self.instruction_data.grace_seconds)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+    ProgramResult,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::AmmError,
+    state::{AmmState, Config},
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct DonateAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DonateAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, user_x_ata, user_y_ata, vault_x, vault_y, config, token_program, ..] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            user,
+            user_x_ata,
+            user_y_ata,
+            vault_x,
+            vault_y,
+            config,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct DonateInstructionData {
+    pub x: u64,
+    pub y: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DonateInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.x == 0 && result.y == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Transfers `x`/`y` from the user into the vaults and mints nothing, raising
+/// the value of every outstanding LP token. Either side may be zero, which
+/// moves the price as a one-sided deposit would. An empty pool is refused,
+/// since the tokens would otherwise go to whoever deposits first.
+pub struct Donate<'a> {
+    pub accounts: DonateAccounts<'a>,
+    pub instruction_data: DonateInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Donate<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DonateAccounts::try_from(accounts)?;
+        let instruction_data = DonateInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Donate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &29;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+        config.check_initialized()?;
+        config.check_no_flash_loan()?;
+
+        // Validate AMM state
+        if config.state() != AmmState::Initialized as u8 {
+            return Err(AmmError::InvalidState.into());
+        }
+
+        // Check vault derivations
+        for (vault, mint) in [
+            (self.accounts.vault_x, config.mint_x()),
+            (self.accounts.vault_y, config.mint_y()),
+        ] {
+            let (expected, _) = find_program_address(
+                &[
+                    self.accounts.config.key(),
+                    self.accounts.token_program.key(),
+                    mint,
+                ],
+                &pinocchio_associated_token_account::ID,
+            );
+            if expected.ne(vault.key()) {
+                return Err(AmmError::InvalidVault.into());
+            }
+        }
+
+        // Only a pool that already holds liquidity has LPs to donate to
+        let reserve_x =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? }.amount();
+        let reserve_y =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? }.amount();
+        if reserve_x == 0 || reserve_y == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Transfer X tokens from user to vault
+        if self.instruction_data.x != 0 {
+            Transfer {
+                from: self.accounts.user_x_ata,
+                to: self.accounts.vault_x,
+                authority: self.accounts.user,
+                amount: self.instruction_data.x,
+            }
+            .invoke()?;
+        }
+
+        // Transfer Y tokens from user to vault
+        if self.instruction_data.y != 0 {
+            Transfer {
+                from: self.accounts.user_y_ata,
+                to: self.accounts.vault_y,
+                authority: self.accounts.user,
+                amount: self.instruction_data.y,
+            }
+            .invoke()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    errors::AmmError,
+    state::{AmmState, Config},
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct FinalizeSunsetAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FinalizeSunsetAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Second half of retiring a pool: once the grace window `BeginSunset`
+/// opened has fully elapsed, clears it so the pool is plainly `Disabled`.
+pub struct FinalizeSunset<'a> {
+    pub accounts: FinalizeSunsetAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FinalizeSunset<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = FinalizeSunsetAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> FinalizeSunset<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &31;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        // The pool must still be in the sunset `BeginSunset` started
+        if config.state() != AmmState::Disabled as u8 {
+            return Err(AmmError::InvalidState.into());
+        }
+
+        // LPs keep their window to exit until it has fully elapsed
+        let now = Clock::get()?.unix_timestamp;
+        if config.effective_state(now) != AmmState::Disabled as u8 {
+            return Err(AmmError::SunsetPending.into());
+        }
+
+        config.disable(config.disabled_at(), 0)
+    }
+}
@@ -0,0 +1,184 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::instructions::Instructions,
+    ProgramResult,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{
+    errors::AmmError,
+    instructions::FlashRepay,
+    state::{AmmState, Config},
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct FlashBorrowAccounts<'a> {
+    pub borrower: &'a AccountInfo,
+    pub borrower_ata: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub instructions_sysvar: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FlashBorrowAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [borrower, borrower_ata, vault, config, token_program, instructions_sysvar, ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self {
+            borrower,
+            borrower_ata,
+            vault,
+            config,
+            token_program,
+            instructions_sysvar,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct FlashBorrowInstructionData {
+    pub is_x: u8,
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for FlashBorrowInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Lends `amount` of X or Y from the pool's vault to `borrower_ata`. A
+/// `FlashRepay` for the same pool must come later in the same transaction;
+/// it settles the loan once the vault holds its pre-borrow balance plus the
+/// flash fee, and the whole transaction reverts otherwise. Until then the
+/// pool refuses swaps, deposits and withdraws.
+pub struct FlashBorrow<'a> {
+    pub accounts: FlashBorrowAccounts<'a>,
+    pub instruction_data: FlashBorrowInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for FlashBorrow<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = FlashBorrowAccounts::try_from(accounts)?;
+        let instruction_data = FlashBorrowInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> FlashBorrow<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &27;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+        config.check_no_flash_loan()?;
+
+        // Validate AMM state
+        if config.state() != AmmState::Initialized as u8 {
+            return Err(AmmError::InvalidState.into());
+        }
+
+        // Check vault derivation
+        let is_x = self.instruction_data.is_x != 0;
+        let mint = match is_x {
+            true => config.mint_x(),
+            false => config.mint_y(),
+        };
+        let (vault, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                mint,
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault.ne(self.accounts.vault.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        self.check_repay_follows()?;
+
+        // The vault must end up back here plus the fee, rounded up
+        let balance =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault)? }.amount();
+        let fee = (self.instruction_data.amount as u128 * config.flash_fee() as u128)
+            .div_ceil(10_000) as u64;
+        let required = balance
+            .checked_add(fee)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        config.open_flash_loan(is_x, required);
+
+        // Build config signer seeds
+        let seed_binding = config.seed().to_le_bytes();
+        let config_bump = config.config_bump();
+        let config_seeds = [
+            Seed::from(b"config"),
+            Seed::from(&seed_binding),
+            Seed::from(config.mint_x().as_ref()),
+            Seed::from(config.mint_y().as_ref()),
+            Seed::from(&config_bump),
+        ];
+        let signer = Signer::from(&config_seeds);
+
+        // Transfer the loan from vault to borrower
+        Transfer {
+            from: self.accounts.vault,
+            to: self.accounts.borrower_ata,
+            authority: self.accounts.config,
+            amount: self.instruction_data.amount,
+        }
+        .invoke_signed(&[signer])
+    }
+
+    /// Fails unless a later instruction in this transaction is this
+    /// program's `FlashRepay` against the same config.
+    fn check_repay_follows(&self) -> ProgramResult {
+        let instructions = Instructions::try_from(self.accounts.instructions_sysvar)?;
+        let mut index = instructions.load_current_index() as usize + 1;
+        while let Ok(instruction) = instructions.load_instruction_at(index) {
+            if instruction.get_program_id() == &crate::ID
+                && instruction.get_instruction_data().first() == Some(FlashRepay::DISCRIMINATOR)
+                && instruction
+                    .get_account_meta_at(FlashRepay::CONFIG_INDEX)
+                    .is_ok_and(|meta| meta.key.eq(self.accounts.config.key()))
+            {
+                return Ok(());
+            }
+            index += 1;
+        }
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
@@ -0,0 +1,119 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+    ProgramResult,
+};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
+
+use crate::{errors::AmmError, state::Config};
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct FlashRepayAccounts<'a> {
+    pub borrower: &'a AccountInfo,
+    pub borrower_ata: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FlashRepayAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [borrower, borrower_ata, vault, config, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self {
+            borrower,
+            borrower_ata,
+            vault,
+            config,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Settles the pool's open flash loan. Whatever the vault still lacks of its
+/// pre-borrow balance plus the flash fee is pulled from `borrower_ata`, so
+/// a borrower may also repay with a plain token transfer beforehand.
+pub struct FlashRepay<'a> {
+    pub accounts: FlashRepayAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for FlashRepay<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = FlashRepayAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> FlashRepay<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &28;
+
+    /// Position of `config` in the accounts, for `FlashBorrow` to match on.
+    pub const CONFIG_INDEX: usize = 3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        let required = config.flash_required();
+        if required == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The vault seeds include the token program, so a substitute program
+        // could derive an address of its own choosing
+        if self.accounts.token_program.key().ne(&pinocchio_token::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Check vault derivation against the side the loan came from
+        let mint = match config.flash_is_x() {
+            true => config.mint_x(),
+            false => config.mint_y(),
+        };
+        let (vault, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                mint,
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault.ne(self.accounts.vault.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        {
+            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
+            if vault.mint().ne(mint) || vault.owner().ne(self.accounts.config.key()) {
+                return Err(AmmError::InvalidVault.into());
+            }
+        }
+
+        // Transfer whatever is still owed from borrower to vault
+        let balance = TokenAccount::from_account_info(self.accounts.vault)?.amount();
+        if balance < required {
+            Transfer {
+                from: self.accounts.borrower_ata,
+                to: self.accounts.vault,
+                authority: self.accounts.borrower,
+                amount: required - balance,
+            }
+            .invoke()?;
+        }
+
+        // Compare against the pre-borrow balance once more, after the transfer
+        let balance = TokenAccount::from_account_info(self.accounts.vault)?.amount();
+        if balance < required {
+            return Err(AmmError::FlashLoanNotRepaid.into());
+        }
+
+        config.close_flash_loan();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError,
+    pubkey::find_program_address, ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::state::Config;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct GetInvariantAccounts<'a> {
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetInvariantAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vault_x, vault_y, config, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            vault_x,
+            vault_y,
+            config,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: writes the pool's current `k = vault_x * vault_y` as a
+/// little-endian `u128` into the transaction return data.
+pub struct GetInvariant<'a> {
+    pub accounts: GetInvariantAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetInvariant<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetInvariantAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetInvariant<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+
+        // Check vault derivations
+        let (vault_x, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_x(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_x.ne(self.accounts.vault_x.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (vault_y, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_y(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Deserialize token accounts
+        let vault_x_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
+        let vault_y_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
+
+        // k never overflows a u128 since both factors fit in a u64
+        let k = (vault_x_account.amount() as u128) * (vault_y_account.amount() as u128);
+
+        set_return_data(&k.to_le_bytes());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError,
+    pubkey::Pubkey, ProgramResult,
+};
+
+use crate::state::Config;
+
+/// Characters of each mint's address shown in the label.
+const LABEL_MINT_CHARS: usize = 4;
+/// `LP-` + mint_x prefix + `-` + mint_y prefix.
+const LABEL_LEN: usize = 3 + LABEL_MINT_CHARS + 1 + LABEL_MINT_CHARS;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct GetLpLabelAccounts<'a> {
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetLpLabelAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self { config })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: writes a display label for the pool's LP mint, such as
+/// `LP-So11-EPjF`, built from the base58 prefixes of the pair's mints.
+/// The label depends only on the pair, so wallets can show it without
+/// metadata.
+pub struct GetLpLabel<'a> {
+    pub accounts: GetLpLabelAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetLpLabel<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetLpLabelAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetLpLabel<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+
+        let mut label = [0u8; LABEL_LEN];
+        label[..3].copy_from_slice(b"LP-");
+        label[3..3 + LABEL_MINT_CHARS].copy_from_slice(&base58_prefix(config.mint_x()));
+        label[3 + LABEL_MINT_CHARS] = b'-';
+        label[4 + LABEL_MINT_CHARS..].copy_from_slice(&base58_prefix(config.mint_y()));
+
+        set_return_data(&label);
+
+        Ok(())
+    }
+}
+
+/// First `LABEL_MINT_CHARS` characters of `key`'s base58 address.
+#[inline(always)]
+pub fn base58_prefix(key: &Pubkey) -> [u8; LABEL_MINT_CHARS] {
+    // Little-endian base58 digits of the key, built by repeated division
+    let mut digits = [0u8; 44];
+    let mut len = 0;
+    for &byte in key.iter() {
+        let mut carry = byte as u32;
+        for digit in digits[..len].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+
+    // Leading zero bytes encode as '1', followed by the digits most
+    // significant first
+    let zeros = key.iter().take_while(|&&b| b == 0).count();
+    let mut prefix = [0u8; LABEL_MINT_CHARS];
+    for (i, c) in prefix.iter_mut().enumerate() {
+        *c = match i < zeros {
+            true => BASE58_ALPHABET[0],
+            false => BASE58_ALPHABET[digits[len - 1 - (i - zeros)] as usize],
+        };
+    }
+    prefix
+}
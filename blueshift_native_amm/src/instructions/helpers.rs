@@ -0,0 +1,529 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{errors::AmmError, state::Config};
+
+// ─── Authority ──────────────────────────────────────────────────────────────
+
+pub struct AuthorityAccount;
+
+impl AuthorityAccount {
+    /// Admin paths must see the pool authority both as the key and as a
+    /// signer; a matching key alone could be passed by anyone.
+    #[inline(always)]
+    pub fn check(authority: &AccountInfo, config: &Config) -> ProgramResult {
+        if !authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        match config.has_authority() {
+            Some(expected) if expected.eq(authority.key()) => Ok(()),
+            _ => Err(ProgramError::MissingRequiredSignature),
+        }
+    }
+}
+
+// ─── Reserves ───────────────────────────────────────────────────────────────
+
+/// Rejects reserve states no valid sequence of instructions can produce, e.g.
+/// LP outstanding against an empty vault. `supply` is `None` for instructions
+/// that don't load the LP mint, in which case only the vaults are compared.
+#[inline(always)]
+pub fn validate_reserves(x: u64, y: u64, supply: Option<u64>) -> ProgramResult {
+    let consistent = match supply {
+        // Empty and pre-seeded pools are left to the first deposit
+        Some(0) => true,
+        Some(_) => x != 0 && y != 0,
+        None => (x == 0) == (y == 0),
+    };
+    if !consistent {
+        return Err(AmmError::PoolInconsistent.into());
+    }
+    Ok(())
+}
+
+// ─── Deadlines ──────────────────────────────────────────────────────────────
+
+/// Fails once the unix timestamp `expiration` has passed; zero sets no
+/// deadline.
+#[inline(always)]
+pub fn check_expiration(expiration: i64) -> ProgramResult {
+    if expiration != 0 && Clock::get()?.unix_timestamp > expiration {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
+
+// ─── Events ─────────────────────────────────────────────────────────────────
+
+/// Size of an `encode_reserves_event` record.
+pub const RESERVES_EVENT_LEN: usize = 1 + 4 * size_of::<u64>();
+
+/// Fixed layout shared by pool events that move reserves: a direction byte,
+/// the amounts in and out, then both reserves afterwards, all little-endian.
+#[inline(always)]
+pub fn encode_reserves_event(
+    is_x: bool,
+    amount_in: u64,
+    amount_out: u64,
+    reserve_x: u64,
+    reserve_y: u64,
+) -> [u8; RESERVES_EVENT_LEN] {
+    let mut event = [0u8; RESERVES_EVENT_LEN];
+    event[0] = is_x as u8;
+    event[1..9].copy_from_slice(&amount_in.to_le_bytes());
+    event[9..17].copy_from_slice(&amount_out.to_le_bytes());
+    event[17..25].copy_from_slice(&reserve_x.to_le_bytes());
+    event[25..33].copy_from_slice(&reserve_y.to_le_bytes());
+    event
+}
+
+// ─── Curve ──────────────────────────────────────────────────────────────────
+
+/// Decimal precision withdraws pass to `constant_product_curve` when
+/// converting LP amounts into reserves. Fixed rather than taken from the LP
+/// mint, whose decimals may be too few to express a withdraw's share.
+pub const CURVE_PRECISION: u32 = 6;
+
+/// LP decimals for pools initialized without choosing them.
+pub const DEFAULT_LP_DECIMALS: u8 = 6;
+pub const MAX_LP_DECIMALS: u8 = 9;
+
+/// Integer square root, rounded down.
+#[inline(always)]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from a power of two at or above the root
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+// ─── Versioning ─────────────────────────────────────────────────────────────
+
+/// Every instruction's data starts with a layout version, so fields can be
+/// added without breaking clients still sending an older layout.
+///
+/// The instruction data structs behind it are `#[repr(C, packed)]` and read
+/// whole with `read_unaligned`. Their fields are only ever used by value;
+/// copy one into a local before borrowing it, since a reference to a packed
+/// field may be unaligned.
+pub const INSTRUCTION_DATA_V1: u8 = 1;
+pub const INSTRUCTION_DATA_V2: u8 = 2;
+pub const INSTRUCTION_DATA_V3: u8 = 3;
+pub const INSTRUCTION_DATA_V4: u8 = 4;
+pub const INSTRUCTION_DATA_V5: u8 = 5;
+
+/// Splits the leading version byte off `data`, rejecting versions newer than
+/// `latest` the instruction knows how to parse.
+#[inline(always)]
+pub fn split_version(data: &[u8], latest: u8) -> Result<(u8, &[u8]), ProgramError> {
+    match data.split_first() {
+        Some((&version, rest)) if version >= INSTRUCTION_DATA_V1 && version <= latest => {
+            Ok((version, rest))
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// ─── Token-2022 ─────────────────────────────────────────────────────────────
+
+// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = [
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+];
+
+/// `decimals` sits at the same offset in legacy and Token-2022 mints.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Decimals of a legacy or Token-2022 mint, read without assuming the
+/// legacy account length.
+#[inline(always)]
+pub fn mint_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    if !mint.is_owned_by(&pinocchio_token::ID) && !mint.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    mint.try_borrow_data()?
+        .get(MINT_DECIMALS_OFFSET)
+        .copied()
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Extensions start after the base account padded to 165 bytes plus the
+/// account-type byte.
+const EXTENSIONS_OFFSET: usize = 166;
+
+// ─── LP Mint ────────────────────────────────────────────────────────────────
+
+/// Token-2022 mint carrying only the NonTransferable extension: the padded
+/// base account, the account-type byte and an empty TLV entry.
+const NON_TRANSFERABLE_MINT_LEN: usize = EXTENSIONS_OFFSET + 4;
+
+const INITIALIZE_MINT_2: u8 = 20;
+const MINT_TO: u8 = 7;
+const BURN: u8 = 8;
+const INITIALIZE_NON_TRANSFERABLE_MINT: u8 = 32;
+
+/// The pool's LP mint. Regular pools use SPL Token; soulbound pools use a
+/// Token-2022 mint with the NonTransferable extension, so LP can only be
+/// minted and burned by the program, never moved between wallets.
+pub struct LpMint;
+
+impl LpMint {
+    /// Token program owning `mint_lp`, which LP CPIs must target.
+    #[inline(always)]
+    pub fn token_program(mint_lp: &AccountInfo) -> Result<&'static Pubkey, ProgramError> {
+        if mint_lp.is_owned_by(&pinocchio_token::ID) {
+            Ok(&pinocchio_token::ID)
+        } else if mint_lp.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+            Ok(&TOKEN_2022_PROGRAM_ID)
+        } else {
+            Err(ProgramError::InvalidAccountOwner)
+        }
+    }
+
+    /// Loads a legacy or Token-2022 LP mint. Both share the legacy base
+    /// layout, and the soulbound mint's extension only ever follows it.
+    ///
+    /// # Safety
+    ///
+    /// No mutable borrow of `mint_lp` may be live, and the returned mint
+    /// must be re-read after any CPI that mints or burns LP.
+    #[inline(always)]
+    pub unsafe fn load_unchecked(
+        mint_lp: &AccountInfo,
+    ) -> Result<&pinocchio_token::state::Mint, ProgramError> {
+        Self::token_program(mint_lp)?;
+        if mint_lp.data_len() < pinocchio_token::state::Mint::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(pinocchio_token::state::Mint::from_bytes(
+            mint_lp.borrow_data_unchecked(),
+        ))
+    }
+
+    /// Loads an LP token account under either token program, as
+    /// `load_unchecked` does the mint.
+    ///
+    /// # Safety
+    ///
+    /// No mutable borrow of `account` may be live, and the returned account
+    /// must be re-read after any CPI that moves LP.
+    #[inline(always)]
+    pub unsafe fn account_unchecked(
+        account: &AccountInfo,
+    ) -> Result<&pinocchio_token::state::TokenAccount, ProgramError> {
+        Self::token_program(account)?;
+        if account.data_len() < pinocchio_token::state::TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(pinocchio_token::state::TokenAccount::from_bytes(
+            account.borrow_data_unchecked(),
+        ))
+    }
+
+    #[inline(always)]
+    pub fn init(
+        payer: &AccountInfo,
+        mint_lp: &AccountInfo,
+        mint_authority: &Pubkey,
+        seeds: &[Seed],
+        decimals: u8,
+        soulbound: bool,
+    ) -> ProgramResult {
+        let (token_program, space) = match soulbound {
+            true => (&TOKEN_2022_PROGRAM_ID, NON_TRANSFERABLE_MINT_LEN),
+            false => (&pinocchio_token::ID, pinocchio_token::state::Mint::LEN),
+        };
+
+        ProgramAccount::create(payer, mint_lp, seeds, space, token_program)?;
+
+        let mint_meta = [AccountMeta::writable(mint_lp.key())];
+
+        // Extensions must be initialized before the mint itself
+        if soulbound {
+            invoke_signed(
+                &Instruction {
+                    program_id: token_program,
+                    accounts: &mint_meta,
+                    data: &[INITIALIZE_NON_TRANSFERABLE_MINT],
+                },
+                &[mint_lp],
+                &[],
+            )?;
+        }
+
+        // Discriminator, decimals, mint authority and an absent freeze authority
+        let mut data = [0u8; 67];
+        data[0] = INITIALIZE_MINT_2;
+        data[1] = decimals;
+        data[2..34].copy_from_slice(mint_authority);
+        invoke_signed(
+            &Instruction {
+                program_id: token_program,
+                accounts: &mint_meta,
+                data: &data,
+            },
+            &[mint_lp],
+            &[],
+        )
+    }
+
+    #[inline(always)]
+    pub fn mint_to(
+        mint_lp: &AccountInfo,
+        account: &AccountInfo,
+        mint_authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let mut data = [0u8; 9];
+        data[0] = MINT_TO;
+        data[1..].copy_from_slice(&amount.to_le_bytes());
+        invoke_signed(
+            &Instruction {
+                program_id: Self::token_program(mint_lp)?,
+                accounts: &[
+                    AccountMeta::writable(mint_lp.key()),
+                    AccountMeta::writable(account.key()),
+                    AccountMeta::readonly_signer(mint_authority.key()),
+                ],
+                data: &data,
+            },
+            &[mint_lp, account, mint_authority],
+            signers,
+        )
+    }
+
+    #[inline(always)]
+    pub fn burn(
+        account: &AccountInfo,
+        mint_lp: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let mut data = [0u8; 9];
+        data[0] = BURN;
+        data[1..].copy_from_slice(&amount.to_le_bytes());
+        invoke_signed(
+            &Instruction {
+                program_id: Self::token_program(mint_lp)?,
+                accounts: &[
+                    AccountMeta::writable(account.key()),
+                    AccountMeta::writable(mint_lp.key()),
+                    AccountMeta::readonly_signer(authority.key()),
+                ],
+                data: &data,
+            },
+            &[account, mint_lp, authority],
+            signers,
+        )
+    }
+}
+
+// ─── NativeSol ──────────────────────────────────────────────────────────────
+
+/// The wrapped SOL mint, So11111111111111111111111111111111111111112.
+pub const NATIVE_MINT: Pubkey = [
+    0x06, 0x9b, 0x88, 0x57, 0xfe, 0xab, 0x81, 0x84, 0xfb, 0x68, 0x7f, 0x63, 0x46, 0x18, 0xc0, 0x35,
+    0xda, 0xc4, 0x39, 0xdc, 0x1a, 0xeb, 0x3b, 0x55, 0x98, 0xa0, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Moves a pool side that is wrapped SOL in and out as plain lamports, so
+/// users never have to wrap or unwrap it themselves.
+pub struct NativeSol;
+
+impl NativeSol {
+    /// Pays `amount` from `user` into `vault`: lamports wrapped in place when
+    /// the side is native, a token transfer out of `user_ata` otherwise. The
+    /// native path invokes the System Program, which must be passed in.
+    #[inline(always)]
+    pub fn transfer_in(
+        is_native: bool,
+        user: &AccountInfo,
+        user_ata: &AccountInfo,
+        vault: &AccountInfo,
+        amount: u64,
+    ) -> ProgramResult {
+        if !is_native {
+            return pinocchio_token::instructions::Transfer {
+                from: user_ata,
+                to: vault,
+                authority: user,
+                amount,
+            }
+            .invoke();
+        }
+        pinocchio_system::instructions::Transfer {
+            from: user,
+            to: vault,
+            lamports: amount,
+        }
+        .invoke()?;
+        pinocchio_token::instructions::SyncNative {
+            native_token: vault,
+        }
+        .invoke()
+    }
+
+    /// Unwraps a native side just paid out to `user_ata` by closing that
+    /// account back to `user`, which returns its whole balance and rent as
+    /// lamports. Does nothing for a token side.
+    #[inline(always)]
+    pub fn unwrap(is_native: bool, user: &AccountInfo, user_ata: &AccountInfo) -> ProgramResult {
+        if !is_native {
+            return Ok(());
+        }
+        pinocchio_token::instructions::CloseAccount {
+            account: user_ata,
+            destination: user,
+            authority: user,
+        }
+        .invoke()
+    }
+}
+
+// ─── AssociatedTokenAccount ─────────────────────────────────────────────────
+
+pub struct AssociatedTokenAccount;
+
+impl AssociatedTokenAccount {
+    #[inline(always)]
+    pub fn init_if_needed(
+        ata: &AccountInfo,
+        mint: &AccountInfo,
+        payer: &AccountInfo,
+        authority: &AccountInfo,
+        system_program: &AccountInfo,
+        token_program: &AccountInfo,
+    ) -> ProgramResult {
+        pinocchio_associated_token_account::instructions::CreateIdempotent {
+            funding_account: payer,
+            account: ata,
+            wallet: authority,
+            mint,
+            system_program,
+            token_program,
+        }
+        .invoke()?;
+        Ok(())
+    }
+}
+
+// ─── ProgramAccount ────────────────────────────────────────────────────────
+
+pub struct ProgramAccount;
+
+impl ProgramAccount {
+    #[inline(always)]
+    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn init(
+        payer: &AccountInfo,
+        account: &AccountInfo,
+        seeds: &[Seed],
+        data_len: usize,
+    ) -> ProgramResult {
+        Self::create(payer, account, seeds, data_len, &crate::ID)
+    }
+
+    /// Creates the PDA `account` signed for by `seeds` with `space` bytes
+    /// owned by `owner`. `CreateAccount` refuses an address that already
+    /// holds lamports, so anyone could block a PDA by funding it first; a
+    /// funded one is instead topped up to rent exemption, then allocated
+    /// and assigned.
+    #[inline(always)]
+    pub fn create(
+        payer: &AccountInfo,
+        account: &AccountInfo,
+        seeds: &[Seed],
+        space: usize,
+        owner: &Pubkey,
+    ) -> ProgramResult {
+        let required = Rent::get()?.minimum_balance(space);
+        let current = account.lamports();
+        if current == 0 {
+            return pinocchio_system::instructions::CreateAccount {
+                from: payer,
+                to: account,
+                lamports: required,
+                space: space as u64,
+                owner,
+            }
+            .invoke_signed(&[Signer::from(seeds)]);
+        }
+
+        if required > current {
+            pinocchio_system::instructions::Transfer {
+                from: payer,
+                to: account,
+                lamports: required - current,
+            }
+            .invoke()?;
+        }
+        pinocchio_system::instructions::Allocate {
+            account,
+            space: space as u64,
+        }
+        .invoke_signed(&[Signer::from(seeds)])?;
+        pinocchio_system::instructions::Assign { account, owner }
+            .invoke_signed(&[Signer::from(seeds)])
+    }
+
+    /// Fails unless `account` is still an uncreated system account, so an
+    /// initializer never runs over one that already exists.
+    #[inline(always)]
+    pub fn check_uninitialized(account: &AccountInfo) -> ProgramResult {
+        if !account.is_owned_by(&pinocchio_system::ID) || !account.data_is_empty() {
+            return Err(AmmError::AlreadyInitialized.into());
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn close(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        let lamports = account.lamports();
+        unsafe {
+            *account.borrow_mut_lamports_unchecked() = 0;
+            let destination_lamports = destination.borrow_mut_lamports_unchecked();
+            *destination_lamports = destination_lamports
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        let mut data = account.try_borrow_mut_data()?;
+        let len = data.len();
+        for byte in data.as_mut()[..len].iter_mut() {
+            *byte = 0;
+        }
+
+        unsafe {
+            account.assign(&pinocchio_system::ID);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::Config;
+
+/// Upper bound on allowlisted mints, keeps the swap-time scan cheap.
+pub const MAX_ALLOWLIST_MINTS: usize = 16;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct InitAllowlistAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub allowlist: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitAllowlistAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, allowlist, system_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self {
+            authority,
+            config,
+            allowlist,
+            system_program,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+pub struct InitAllowlistInstructionData<'a> {
+    pub allowlist_bump: [u8; 1],
+    pub mints: &'a [u8],
+}
+
+impl<'a> TryFrom<&'a [u8]> for InitAllowlistInstructionData<'a> {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        let Some((allowlist_bump, mints)) = data.split_first() else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        if mints.is_empty()
+            || mints.len() % size_of::<Pubkey>() != 0
+            || mints.len() / size_of::<Pubkey>() > MAX_ALLOWLIST_MINTS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(Self {
+            allowlist_bump: [*allowlist_bump],
+            mints,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Creates the pool's output-mint allowlist and points the config at it.
+/// Swaps then only deliver mints on the list.
+pub struct InitAllowlist<'a> {
+    pub accounts: InitAllowlistAccounts<'a>,
+    pub instruction_data: InitAllowlistInstructionData<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for InitAllowlist<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = InitAllowlistAccounts::try_from(accounts)?;
+        let instruction_data = InitAllowlistInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> InitAllowlist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can restrict outputs, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        if config.has_allowlist().is_some() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Create the allowlist PDA
+        let space = self.instruction_data.mints.len();
+        let allowlist_seeds = [
+            Seed::from(b"allowlist"),
+            Seed::from(self.accounts.config.key().as_ref()),
+            Seed::from(&self.instruction_data.allowlist_bump),
+        ];
+        let signer = Signer::from(&allowlist_seeds);
+
+        pinocchio_system::instructions::CreateAccount {
+            from: self.accounts.authority,
+            to: self.accounts.allowlist,
+            lamports: Rent::get()?.minimum_balance(space),
+            space: space as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[signer])?;
+
+        self.accounts
+            .allowlist
+            .try_borrow_mut_data()?
+            .copy_from_slice(self.instruction_data.mints);
+
+        config.set_allowlist(*self.accounts.allowlist.key());
+
+        Ok(())
+    }
+}
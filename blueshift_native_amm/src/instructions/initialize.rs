@@ -4,12 +4,21 @@ use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    sysvars::{rent::Rent, Sysvar},
+    pubkey::find_program_address,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::instructions::InitializeMint2;
 
-use crate::state::Config;
+use pinocchio_token::state::TokenAccount;
+
+use crate::{errors::AmmError, instructions::MINIMUM_LIQUIDITY, state::Config};
+
+use super::helpers::*;
+
+/// Widest gap allowed between the pool mints' decimals when the initializer
+/// doesn't set one. Wider spreads leave one side with too little precision
+/// for the curve.
+pub const DEFAULT_MAX_DECIMALS_SPREAD: u8 = 9;
 
 // ─── Accounts ───────────────────────────────────────────────────────────────
 
@@ -19,20 +28,45 @@ pub struct InitializeAccounts<'a> {
     pub config: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    pub mint_x: &'a AccountInfo,
+    pub mint_y: &'a AccountInfo,
+    /// The pool's vaults, created or not, checked so a pre-seeded pool is
+    /// always reconciled at initialization.
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    /// The initializer's LP ATA and the config PDA's LP ATA, following the
+    /// vaults. Only needed when the vaults are pre-seeded.
+    pub lp_atas: Option<(&'a AccountInfo, &'a AccountInfo)>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [initializer, mint_lp, config, system_program, token_program, ..] = accounts else {
+        let [initializer, mint_lp, config, system_program, token_program, mint_x, mint_y, vault_x, vault_y, remaining @ ..] =
+            accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
+        // The payer must be a real account, a PDA being created can't fund itself
+        if initializer.key() == config.key() || initializer.key() == mint_lp.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
         Ok(Self {
             initializer,
             mint_lp,
             config,
             system_program,
             token_program,
+            mint_x,
+            mint_y,
+            vault_x,
+            vault_y,
+            lp_atas: match remaining {
+                [initializer_lp_ata, locked_lp_ata, ..] => {
+                    Some((initializer_lp_ata, locked_lp_ata))
+                }
+                _ => None,
+            },
         })
     }
 }
@@ -47,32 +81,70 @@ pub struct InitializeInstructionData {
     pub mint_y: [u8; 32],
     pub config_bump: [u8; 1],
     pub lp_bump: [u8; 1],
+    pub min_lp_mint: u64,
+    pub protocol_fee_recipient: [u8; 32],
+    pub min_reserve_x: u64,
+    pub min_reserve_y: u64,
+    pub soulbound_lp: u8,
+    pub launch_window: i64,
+    pub launch_max_input_bps: u16,
+    pub max_referral_bps: u16,
+    pub curve_type: u8,
     pub authority: [u8; 32],
+    /// Added in v2, which always carries `authority` (zeroed for none).
+    /// v1 pools get `DEFAULT_LP_DECIMALS`.
+    pub lp_decimals: u8,
+    /// Added in v3: the widest gap allowed between the pool mints' decimals.
+    /// Earlier versions get `DEFAULT_MAX_DECIMALS_SPREAD`.
+    pub max_decimals_spread: u8,
 }
 
 impl TryFrom<&[u8]> for InitializeInstructionData {
     type Error = ProgramError;
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        const INITIALIZE_DATA_LEN_WITH_AUTHORITY: usize = size_of::<InitializeInstructionData>();
+        let (version, data) = split_version(data, INSTRUCTION_DATA_V3)?;
+        Self::parse(version, data)
+    }
+}
+
+impl InitializeInstructionData {
+    /// Reads the `version` layout, without its version byte, filling in the
+    /// defaults for whatever that version predates.
+    pub fn parse(version: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        const INITIALIZE_DATA_LEN_V3: usize = size_of::<InitializeInstructionData>();
+        const INITIALIZE_DATA_LEN_V2: usize = INITIALIZE_DATA_LEN_V3 - size_of::<u8>();
+        const INITIALIZE_DATA_LEN_WITH_AUTHORITY: usize = INITIALIZE_DATA_LEN_V2 - size_of::<u8>();
         const INITIALIZE_DATA_LEN: usize =
             INITIALIZE_DATA_LEN_WITH_AUTHORITY - size_of::<[u8; 32]>();
 
-        match data.len() {
-            INITIALIZE_DATA_LEN_WITH_AUTHORITY => {
-                Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+        // v1 carries the trailing authority in full or not at all, and only an
+        // absent one is zero-filled. Any other length, e.g. a truncated
+        // authority, is rejected rather than padded into a different key
+        let len = match (version, data.len()) {
+            (INSTRUCTION_DATA_V1, INITIALIZE_DATA_LEN_WITH_AUTHORITY) => {
+                INITIALIZE_DATA_LEN_WITH_AUTHORITY
             }
-            INITIALIZE_DATA_LEN => {
-                let mut raw: MaybeUninit<[u8; INITIALIZE_DATA_LEN_WITH_AUTHORITY]> =
-                    MaybeUninit::uninit();
-                let raw_ptr = raw.as_mut_ptr() as *mut u8;
-                unsafe {
-                    core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, INITIALIZE_DATA_LEN);
-                    core::ptr::write_bytes(raw_ptr.add(INITIALIZE_DATA_LEN), 0, 32);
-                    Ok((raw.as_ptr() as *const Self).read_unaligned())
-                }
+            (INSTRUCTION_DATA_V1, INITIALIZE_DATA_LEN) => INITIALIZE_DATA_LEN,
+            (INSTRUCTION_DATA_V2, INITIALIZE_DATA_LEN_V2) => INITIALIZE_DATA_LEN_V2,
+            (INSTRUCTION_DATA_V3, INITIALIZE_DATA_LEN_V3) => INITIALIZE_DATA_LEN_V3,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        let mut result = {
+            let mut raw: MaybeUninit<[u8; INITIALIZE_DATA_LEN_V3]> = MaybeUninit::uninit();
+            let raw_ptr = raw.as_mut_ptr() as *mut u8;
+            unsafe {
+                core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, len);
+                core::ptr::write_bytes(raw_ptr.add(len), 0, INITIALIZE_DATA_LEN_V3 - len);
+                (raw.as_ptr() as *const Self).read_unaligned()
             }
-            _ => Err(ProgramError::InvalidInstructionData),
+        };
+        if version == INSTRUCTION_DATA_V1 {
+            result.lp_decimals = DEFAULT_LP_DECIMALS;
         }
+        if version < INSTRUCTION_DATA_V3 {
+            result.max_decimals_spread = DEFAULT_MAX_DECIMALS_SPREAD;
+        }
+        Ok(result)
     }
 }
 
@@ -99,7 +171,30 @@ impl<'a> Initialize<'a> {
     pub const DISCRIMINATOR: &'a u8 = &0;
 
     pub fn process(&mut self) -> ProgramResult {
-        let rent = Rent::get()?;
+        // Check the pool mints. The vaults are only ever moved with SPL Token
+        // instructions, so a Token-2022 mint could never trade
+        if self.accounts.mint_x.key().ne(&self.instruction_data.mint_x)
+            || self.accounts.mint_y.key().ne(&self.instruction_data.mint_y)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !self.accounts.mint_x.is_owned_by(&pinocchio_token::ID)
+            || !self.accounts.mint_y.is_owned_by(&pinocchio_token::ID)
+        {
+            return Err(AmmError::UnsupportedMint.into());
+        }
+        if mint_decimals(self.accounts.mint_x)?.abs_diff(mint_decimals(self.accounts.mint_y)?)
+            > self.instruction_data.max_decimals_spread
+        {
+            return Err(AmmError::DecimalsSpreadTooWide.into());
+        }
+
+        let (seeded_x, seeded_y) = self.check_vaults()?;
+
+        // Refuse a pool that already exists before creating anything, rather
+        // than surface whatever the system program makes of it
+        ProgramAccount::check_uninitialized(self.accounts.config)?;
+        ProgramAccount::check_uninitialized(self.accounts.mint_lp)?;
 
         // 1. Create Config account
         let seed_binding = self.instruction_data.seed.to_le_bytes();
@@ -110,19 +205,15 @@ impl<'a> Initialize<'a> {
             Seed::from(&self.instruction_data.mint_y),
             Seed::from(&self.instruction_data.config_bump),
         ];
-        let config_signer = Signer::from(&config_seeds);
-
-        pinocchio_system::instructions::CreateAccount {
-            from: self.accounts.initializer,
-            to: self.accounts.config,
-            lamports: rent.minimum_balance(Config::LEN),
-            space: Config::LEN as u64,
-            owner: &crate::ID,
-        }
-        .invoke_signed(&[config_signer])?;
+        ProgramAccount::init(
+            self.accounts.initializer,
+            self.accounts.config,
+            &config_seeds,
+            Config::LEN,
+        )?;
 
         // 2. Populate Config
-        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        let config = unsafe { Config::init(self.accounts.config)? };
         config.set_inner(
             self.instruction_data.seed,
             self.instruction_data.authority,
@@ -131,33 +222,143 @@ impl<'a> Initialize<'a> {
             self.instruction_data.fee,
             self.instruction_data.config_bump,
         )?;
+        config.set_min_lp_mint(self.instruction_data.min_lp_mint);
+        config.set_protocol_fee_recipient(self.instruction_data.protocol_fee_recipient);
+        config.set_min_reserve_x(self.instruction_data.min_reserve_x);
+        config.set_min_reserve_y(self.instruction_data.min_reserve_y);
+        let now = Clock::get()?.unix_timestamp;
+        config.set_created_ts(now);
+        config.update_price_cumulatives(0, 0, now);
+        config.set_launch_protection(
+            self.instruction_data.launch_window,
+            self.instruction_data.launch_max_input_bps,
+        )?;
+        config.set_max_referral_bps(self.instruction_data.max_referral_bps)?;
+        config.set_curve_type(self.instruction_data.curve_type)?;
+        config.set_lp_bump(self.instruction_data.lp_bump);
+        config.set_lp_decimals(self.instruction_data.lp_decimals)?;
+        config.set_native(
+            self.instruction_data.mint_x == NATIVE_MINT,
+            self.instruction_data.mint_y == NATIVE_MINT,
+        );
 
-        // 3. Create mint_lp account
+        // 3. Create and initialize mint_lp with config as mint_authority
         let mint_lp_seeds = [
             Seed::from(b"mint_lp"),
             Seed::from(self.accounts.config.key().as_ref()),
             Seed::from(&self.instruction_data.lp_bump),
         ];
-        let mint_signer = Signer::from(&mint_lp_seeds);
-
-        pinocchio_system::instructions::CreateAccount {
-            from: self.accounts.initializer,
-            to: self.accounts.mint_lp,
-            lamports: rent.minimum_balance(82),
-            space: 82u64,
-            owner: &pinocchio_token::ID,
-        }
-        .invoke_signed(&[mint_signer])?;
-
-        // 4. Initialize mint_lp with config as mint_authority
-        InitializeMint2 {
-            mint: self.accounts.mint_lp,
-            decimals: 6,
-            mint_authority: self.accounts.config.key(),
-            freeze_authority: None,
+        LpMint::init(
+            self.accounts.initializer,
+            self.accounts.mint_lp,
+            self.accounts.config.key(),
+            &mint_lp_seeds,
+            self.instruction_data.lp_decimals,
+            self.instruction_data.soulbound_lp != 0,
+        )?;
+
+        // 4. Mint LP against reserves already sitting in the vaults
+        if seeded_x != 0 {
+            self.mint_seeded_lp(seeded_x, seeded_y, Signer::from(&config_seeds))?;
         }
-        .invoke()?;
 
         Ok(())
     }
+
+    /// Mints LP for a pool whose vaults were funded before it existed, as a
+    /// first deposit of those reserves would: `sqrt(x * y)` in total, of
+    /// which `MINIMUM_LIQUIDITY` is locked in the config PDA's LP ATA and the
+    /// rest goes to the initializer. Without it the reserves would have no
+    /// LP against them and be captured by the first depositor.
+    fn mint_seeded_lp(&self, x: u64, y: u64, signer: Signer) -> ProgramResult {
+        let (initializer_lp_ata, locked_lp_ata) = self
+            .accounts
+            .lp_atas
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let (expected, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                LpMint::token_program(self.accounts.mint_lp)?,
+                self.accounts.mint_lp.key(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if expected.ne(locked_lp_ata.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let amount = isqrt(x as u128 * y as u128) as u64;
+        if amount <= MINIMUM_LIQUIDITY {
+            return Err(AmmError::BelowMinimumLiquidity.into());
+        }
+
+        // Soulbound pools need the ATAs created beforehand, as for deposits
+        if self
+            .accounts
+            .mint_lp
+            .is_owned_by(self.accounts.token_program.key())
+        {
+            for (ata, authority) in [
+                (initializer_lp_ata, self.accounts.initializer),
+                (locked_lp_ata, self.accounts.config),
+            ] {
+                AssociatedTokenAccount::init_if_needed(
+                    ata,
+                    self.accounts.mint_lp,
+                    self.accounts.initializer,
+                    authority,
+                    self.accounts.system_program,
+                    self.accounts.token_program,
+                )?;
+            }
+        }
+
+        LpMint::mint_to(
+            self.accounts.mint_lp,
+            locked_lp_ata,
+            self.accounts.config,
+            MINIMUM_LIQUIDITY,
+            &[signer.clone()],
+        )?;
+        LpMint::mint_to(
+            self.accounts.mint_lp,
+            initializer_lp_ata,
+            self.accounts.config,
+            amount - MINIMUM_LIQUIDITY,
+            &[signer],
+        )
+    }
+
+    /// Reconciles vaults funded ahead of initialization, returning what they
+    /// hold. A one-sided vault is inconsistent and rejected; two funded
+    /// vaults are accepted and get LP minted against them.
+    fn check_vaults(&self) -> Result<(u64, u64), ProgramError> {
+        let (vault_x, vault_y) = (self.accounts.vault_x, self.accounts.vault_y);
+        let config = self.accounts.config.key();
+        let token_program = self.accounts.token_program.key();
+        for (vault, mint) in [
+            (vault_x, &self.instruction_data.mint_x),
+            (vault_y, &self.instruction_data.mint_y),
+        ] {
+            let (expected, _) = find_program_address(
+                &[config, token_program, mint],
+                &pinocchio_associated_token_account::ID,
+            );
+            if expected.ne(vault.key()) {
+                return Err(AmmError::InvalidVault.into());
+            }
+        }
+
+        // A vault nobody has created yet holds nothing
+        let balance = |vault: &AccountInfo| -> Result<u64, ProgramError> {
+            if vault.data_is_empty() {
+                return Ok(0);
+            }
+            Ok(TokenAccount::from_account_info(vault)?.amount())
+        };
+        let (x, y) = (balance(vault_x)?, balance(vault_y)?);
+
+        validate_reserves(x, y, None)?;
+        Ok((x, y))
+    }
 }
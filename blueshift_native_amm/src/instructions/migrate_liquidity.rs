@@ -0,0 +1,236 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    errors::AmmError,
+    instructions::{
+        Deposit, DepositAccounts, DepositInstructionData, Withdraw, WithdrawAccounts,
+        WithdrawInstructionData,
+    },
+    state::Config,
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct MigrateLiquidityAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub mint_lp_from: &'a AccountInfo,
+    pub vault_x_from: &'a AccountInfo,
+    pub vault_y_from: &'a AccountInfo,
+    pub user_lp_ata_from: &'a AccountInfo,
+    pub config_from: &'a AccountInfo,
+    pub mint_lp_to: &'a AccountInfo,
+    pub vault_x_to: &'a AccountInfo,
+    pub vault_y_to: &'a AccountInfo,
+    pub user_lp_ata_to: &'a AccountInfo,
+    pub config_to: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateLiquidityAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, user_x_ata, user_y_ata, mint_lp_from, vault_x_from, vault_y_from, user_lp_ata_from, config_from, mint_lp_to, vault_x_to, vault_y_to, user_lp_ata_to, config_to, token_program, system_program, ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if config_from.key() == config_to.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            user,
+            user_x_ata,
+            user_y_ata,
+            mint_lp_from,
+            vault_x_from,
+            vault_y_from,
+            user_lp_ata_from,
+            config_from,
+            mint_lp_to,
+            vault_x_to,
+            vault_y_to,
+            user_lp_ata_to,
+            config_to,
+            token_program,
+            system_program,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct MigrateLiquidityInstructionData {
+    /// LP of the source pool to move.
+    pub amount: u64,
+    /// Least LP of the destination pool to end up with.
+    pub min_lp: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for MigrateLiquidityInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 || result.min_lp == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        check_expiration(result.expiration)?;
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Moves liquidity between two pools of the same pair in one transaction: a
+/// `Withdraw` from the source pool followed by a `Deposit` of as much of the
+/// released X/Y as the destination's reserve ratio takes. Only `min_lp` is
+/// enforced; whatever the destination can't take stays in the user's ATAs.
+/// The destination must already hold liquidity, and positions are not
+/// tracked.
+pub struct MigrateLiquidity<'a> {
+    pub accounts: MigrateLiquidityAccounts<'a>,
+    pub instruction_data: MigrateLiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for MigrateLiquidity<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = MigrateLiquidityAccounts::try_from(accounts)?;
+        let instruction_data = MigrateLiquidityInstructionData::try_from(data)?;
+
+        // Create the user's destination LP ATA if needed, as Deposit does
+        if accounts
+            .mint_lp_to
+            .is_owned_by(accounts.token_program.key())
+        {
+            AssociatedTokenAccount::init_if_needed(
+                accounts.user_lp_ata_to,
+                accounts.mint_lp_to,
+                accounts.user,
+                accounts.user,
+                accounts.system_program,
+                accounts.token_program,
+            )?;
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> MigrateLiquidity<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Both pools must trade the same pair
+        {
+            let config_from = unsafe { Config::load(self.accounts.config_from)? };
+            let config_to = unsafe { Config::load(self.accounts.config_to)? };
+            if config_from.mint_x() != config_to.mint_x()
+                || config_from.mint_y() != config_to.mint_y()
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // Read ahead of the Withdraw and Deposit that validate these accounts,
+        // so the loads check they are token accounts themselves
+        let balance = |account: &AccountInfo| -> Result<u64, ProgramError> {
+            Ok(TokenAccount::from_account_info(account)?.amount())
+        };
+        let x_before = balance(self.accounts.user_x_ata)?;
+        let y_before = balance(self.accounts.user_y_ata)?;
+        let lp_before = balance(self.accounts.user_lp_ata_to)?;
+
+        // min_lp on the destination stands in for per-token minimums
+        Withdraw {
+            accounts: WithdrawAccounts {
+                user: self.accounts.user,
+                mint_lp: self.accounts.mint_lp_from,
+                vault_x: self.accounts.vault_x_from,
+                vault_y: self.accounts.vault_y_from,
+                user_x_ata: self.accounts.user_x_ata,
+                user_y_ata: self.accounts.user_y_ata,
+                user_lp_ata: self.accounts.user_lp_ata_from,
+                config: self.accounts.config_from,
+                token_program: self.accounts.token_program,
+                position: None,
+            },
+            instruction_data: WithdrawInstructionData {
+                amount: self.instruction_data.amount,
+                min_x: 0,
+                min_y: 0,
+                expiration: self.instruction_data.expiration,
+                allow_zero_slippage: 1,
+            },
+        }
+        .process()?;
+
+        // Deposit as much LP as the released X/Y cover at the destination
+        let x = balance(self.accounts.user_x_ata)?.saturating_sub(x_before);
+        let y = balance(self.accounts.user_y_ata)?.saturating_sub(y_before);
+        let supply = unsafe { LpMint::load_unchecked(self.accounts.mint_lp_to)? }.supply();
+        let reserve_x = balance(self.accounts.vault_x_to)?;
+        let reserve_y = balance(self.accounts.vault_y_to)?;
+        if supply == 0 || reserve_x == 0 || reserve_y == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let lp = core::cmp::min(
+            x as u128 * supply as u128 / reserve_x as u128,
+            y as u128 * supply as u128 / reserve_y as u128,
+        ) as u64;
+        if lp == 0 {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Deposit {
+            accounts: DepositAccounts {
+                user: self.accounts.user,
+                mint_lp: self.accounts.mint_lp_to,
+                vault_x: self.accounts.vault_x_to,
+                vault_y: self.accounts.vault_y_to,
+                user_x_ata: self.accounts.user_x_ata,
+                user_y_ata: self.accounts.user_y_ata,
+                user_lp_ata: self.accounts.user_lp_ata_to,
+                config: self.accounts.config_to,
+                token_program: self.accounts.token_program,
+                system_program: self.accounts.system_program,
+                position: None,
+                remaining: &[],
+            },
+            instruction_data: DepositInstructionData {
+                amount: lp,
+                max_x: x,
+                max_y: y,
+                expiration: self.instruction_data.expiration,
+                // Bounded below on the LP actually received instead
+                min_lp_out: 0,
+            },
+        }
+        .process()?;
+
+        // Check for slippage on the LP actually received
+        let lp_after = balance(self.accounts.user_lp_ata_to)?;
+        if lp_after.saturating_sub(lp_before) < self.instruction_data.min_lp {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,67 @@
+pub mod batch_initialize;
+pub mod begin_sunset;
+pub mod close_config;
+pub mod close_position;
+pub mod collect_protocol_fees;
 pub mod deposit;
+pub mod deposit_single;
+pub mod disable;
+pub mod donate;
+pub mod finalize_sunset;
+pub mod flash_borrow;
+pub mod flash_repay;
+pub mod get_invariant;
+pub mod get_lp_label;
+pub mod helpers;
+pub mod init_allowlist;
 pub mod initialize;
+pub mod migrate_liquidity;
+pub mod open_position;
+pub mod preview_deposit;
+pub mod quote;
+pub mod quote_deposit;
+pub mod renounce_authority;
+pub mod set_state;
 pub mod swap;
+pub mod swap_and_pay;
+pub mod swap_split;
+pub mod sweep_lamports;
+pub mod update_authority;
+pub mod update_fee;
+pub mod update_protocol_fee;
+pub mod update_risk_params;
 pub mod withdraw;
 
+pub use batch_initialize::*;
+pub use begin_sunset::*;
+pub use close_config::*;
+pub use close_position::*;
+pub use collect_protocol_fees::*;
 pub use deposit::*;
+pub use deposit_single::*;
+pub use disable::*;
+pub use donate::*;
+pub use finalize_sunset::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use get_invariant::*;
+pub use get_lp_label::*;
+pub use helpers::*;
+pub use init_allowlist::*;
 pub use initialize::*;
+pub use migrate_liquidity::*;
+pub use open_position::*;
+pub use preview_deposit::*;
+pub use quote::*;
+pub use quote_deposit::*;
+pub use renounce_authority::*;
+pub use set_state::*;
 pub use swap::*;
+pub use swap_and_pay::*;
+pub use swap_split::*;
+pub use sweep_lamports::*;
+pub use update_authority::*;
+pub use update_fee::*;
+pub use update_protocol_fee::*;
+pub use update_risk_params::*;
 pub use withdraw::*;
@@ -0,0 +1,92 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    pubkey::find_program_address, ProgramResult,
+};
+
+use crate::state::{Config, Position};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct OpenPositionAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub position: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for OpenPositionAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, position, config, system_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !user.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(Self {
+            user,
+            position,
+            config,
+            system_program,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Creates the user's `Position` for a pool. Deposits and withdraws that pass
+/// it as a trailing account keep its `lp_amount` in sync.
+pub struct OpenPosition<'a> {
+    pub accounts: OpenPositionAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for OpenPosition<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = OpenPositionAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> OpenPosition<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Positions can only be opened against a real pool
+        unsafe { Config::load(self.accounts.config)? }.check_initialized()?;
+
+        let (_, bump) = find_program_address(
+            &[
+                b"position",
+                self.accounts.config.key(),
+                self.accounts.user.key(),
+            ],
+            &crate::ID,
+        );
+        let bump_binding = [bump];
+        let position_seeds = [
+            Seed::from(b"position"),
+            Seed::from(self.accounts.config.key().as_ref()),
+            Seed::from(self.accounts.user.key().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init(
+            self.accounts.user,
+            self.accounts.position,
+            &position_seeds,
+            Position::LEN,
+        )?;
+
+        let position = unsafe { Position::load_mut(self.accounts.position)? };
+        position.set_inner(
+            *self.accounts.user.key(),
+            *self.accounts.config.key(),
+            bump_binding,
+        );
+
+        Ok(())
+    }
+}
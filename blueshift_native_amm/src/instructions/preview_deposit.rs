@@ -0,0 +1,143 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError,
+    pubkey::find_program_address, ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{instructions::deposit_amounts, state::Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct PreviewDepositAccounts<'a> {
+    pub mint_lp: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for PreviewDepositAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [mint_lp, vault_x, vault_y, config, token_program, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            mint_lp,
+            vault_x,
+            vault_y,
+            config,
+            token_program,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct PreviewDepositInstructionData {
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for PreviewDepositInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: writes the `x` and `y` a `Deposit` of `amount` LP would charge
+/// at current reserves into the return data, as two little-endian `u64`s.
+pub struct PreviewDeposit<'a> {
+    pub accounts: PreviewDepositAccounts<'a>,
+    pub instruction_data: PreviewDepositInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for PreviewDeposit<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = PreviewDepositAccounts::try_from(accounts)?;
+        let instruction_data = PreviewDepositInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> PreviewDeposit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+
+        // Check vault derivations
+        let (vault_x, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_x(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_x.ne(self.accounts.vault_x.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (vault_y, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_y(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Deserialize token accounts
+        let mint_lp = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? };
+        let vault_x_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
+        let vault_y_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
+
+        // Reject pools left in an impossible state
+        validate_reserves(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Some(mint_lp.supply()),
+        )?;
+
+        // An empty pool has no price yet, the first depositor sets it
+        let (x, y) = deposit_amounts(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            mint_lp.supply(),
+            self.instruction_data.amount,
+        )?
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+        let mut result = [0u8; 16];
+        result[..8].copy_from_slice(&x.to_le_bytes());
+        result[8..].copy_from_slice(&y.to_le_bytes());
+        set_return_data(&result);
+
+        Ok(())
+    }
+}
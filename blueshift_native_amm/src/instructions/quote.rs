@@ -0,0 +1,134 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError, ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use constant_product_curve::{ConstantProduct, LiquidityPair};
+
+use crate::{errors::AmmError, state::Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct QuoteAccounts<'a> {
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for QuoteAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vault_x, vault_y, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok(Self {
+            vault_x,
+            vault_y,
+            config,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct QuoteInstructionData {
+    pub is_x: u8,
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for QuoteInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: writes the output a `Swap` of `amount` would receive at current
+/// reserves and fee into the return data, as a little-endian `u64`.
+///
+/// Without the token program to derive vault addresses from, the vaults are
+/// checked by content instead: each must hold its pool mint under the
+/// config's authority.
+pub struct Quote<'a> {
+    pub accounts: QuoteAccounts<'a>,
+    pub instruction_data: QuoteInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Quote<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = QuoteAccounts::try_from(accounts)?;
+        let instruction_data = QuoteInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Quote<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+        config.check_curve()?;
+
+        // Check the vaults belong to this pool
+        let vault_x_account = TokenAccount::from_account_info(self.accounts.vault_x)?;
+        if vault_x_account.mint() != config.mint_x()
+            || vault_x_account.owner() != self.accounts.config.key()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let vault_y_account = TokenAccount::from_account_info(self.accounts.vault_y)?;
+        if vault_y_account.mint() != config.mint_y()
+            || vault_y_account.owner() != self.accounts.config.key()
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Reject pools left in an impossible state
+        validate_reserves(vault_x_account.amount(), vault_y_account.amount(), None)?;
+
+        // The LP supply only matters to deposits and withdraws, so the curve's
+        // own estimate stands in for it
+        let l = ConstantProduct::l_from_xy(vault_x_account.amount(), vault_y_account.amount())
+            .map_err(|_| AmmError::CurveError)?;
+        let mut curve = ConstantProduct::init(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            l,
+            config.fee(),
+            None,
+        )
+        .map_err(|_| AmmError::CurveError)?;
+
+        let p = match self.instruction_data.is_x != 0 {
+            true => LiquidityPair::X,
+            false => LiquidityPair::Y,
+        };
+
+        let swap_result = curve
+            .swap_unsafe(p, self.instruction_data.amount)
+            .map_err(|_| AmmError::CurveError)?;
+
+        set_return_data(&swap_result.withdraw.to_le_bytes());
+
+        Ok(())
+    }
+}
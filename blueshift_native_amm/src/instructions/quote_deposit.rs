@@ -0,0 +1,139 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, program::set_return_data, program_error::ProgramError,
+    pubkey::find_program_address, ProgramResult,
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    instructions::{deposit_amounts, PreviewDepositAccounts},
+    state::Config,
+};
+
+use super::helpers::*;
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct QuoteDepositInstructionData {
+    pub is_x: u8,
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for QuoteDepositInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Read-only: for a target `amount` of one token, writes the LP a balanced
+/// `Deposit` would mint and the `x` and `y` it would charge, as three
+/// little-endian `u64`s. Takes the same accounts as `PreviewDeposit`.
+pub struct QuoteDeposit<'a> {
+    pub accounts: PreviewDepositAccounts<'a>,
+    pub instruction_data: QuoteDepositInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for QuoteDeposit<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = PreviewDepositAccounts::try_from(accounts)?;
+        let instruction_data = QuoteDepositInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> QuoteDeposit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+
+        // Check vault derivations
+        let (vault_x, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_x(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_x.ne(self.accounts.vault_x.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (vault_y, _) = find_program_address(
+            &[
+                self.accounts.config.key(),
+                self.accounts.token_program.key(),
+                config.mint_y(),
+            ],
+            &pinocchio_associated_token_account::ID,
+        );
+        if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Deserialize token accounts
+        let mint_lp = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? };
+        let vault_x_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
+        let vault_y_account =
+            unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
+
+        // Reject pools left in an impossible state
+        validate_reserves(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Some(mint_lp.supply()),
+        )?;
+
+        // LP proportional to the target's share of its reserve, rounded down
+        let reserve = match self.instruction_data.is_x != 0 {
+            true => vault_x_account.amount(),
+            false => vault_y_account.amount(),
+        };
+        if reserve == 0 {
+            // An empty pool has no price yet, the first depositor sets it
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let lp =
+            (self.instruction_data.amount as u128) * (mint_lp.supply() as u128) / (reserve as u128);
+        let lp = u64::try_from(lp).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        if lp == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Price the LP exactly as Deposit would
+        let (x, y) = deposit_amounts(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            mint_lp.supply(),
+            lp,
+        )?
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+        let mut result = [0u8; 24];
+        result[..8].copy_from_slice(&lp.to_le_bytes());
+        result[8..16].copy_from_slice(&x.to_le_bytes());
+        result[16..].copy_from_slice(&y.to_le_bytes());
+        set_return_data(&result);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::state::{AmmState, Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct RenounceAuthorityAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RenounceAuthorityAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct RenounceAuthorityInstructionData {
+    pub final_state: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RenounceAuthorityInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        // A renounced pool either keeps trading or is sunset for good
+        if result.final_state != AmmState::Initialized as u8
+            && result.final_state != AmmState::WithdrawOnly as u8
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Clears the pool authority and pins its final state. With no authority left
+/// nothing can change the state afterwards.
+pub struct RenounceAuthority<'a> {
+    pub accounts: RenounceAuthorityAccounts<'a>,
+    pub instruction_data: RenounceAuthorityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RenounceAuthority<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = RenounceAuthorityAccounts::try_from(accounts)?;
+        let instruction_data = RenounceAuthorityInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RenounceAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        if config.state() == AmmState::Disabled as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        config.set_state(self.instruction_data.final_state)?;
+        config.set_authority([0u8; 32]);
+
+        Ok(())
+    }
+}
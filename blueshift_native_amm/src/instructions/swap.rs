@@ -1,72 +1,156 @@
-use core::mem::size_of;
+use core::mem::{size_of, MaybeUninit};
 
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
+    log::sol_log_data,
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{create_program_address, find_program_address, Pubkey},
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 use pinocchio_token::{instructions::Transfer, state::TokenAccount};
 
-use constant_product_curve::{ConstantProduct, LiquidityPair};
+use constant_product_curve::{ConstantProduct, CurveError, LiquidityPair};
 
-use crate::state::{AmmState, Config};
+use crate::{
+    errors::AmmError,
+    instructions::find_fee_authority,
+    state::{Allowlist, AmmState, Config},
+};
+
+use super::helpers::*;
 
 // ─── Accounts ───────────────────────────────────────────────────────────────
 
 pub struct SwapAccounts<'a> {
     pub user: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
     pub user_x_ata: &'a AccountInfo,
     pub user_y_ata: &'a AccountInfo,
     pub vault_x: &'a AccountInfo,
     pub vault_y: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    /// Trailing accounts. When `referral_bps` is set the first one is the
+    /// referrer's ATA; the allowlist, the protocol fee vault and the System
+    /// Program are found among them by address, and only needed when the
+    /// pool's allowlist, protocol fee or native input calls for them.
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> SwapAccounts<'a> {
+    /// The referrer's ATA, which holds the first trailing slot whenever a
+    /// referral is paid.
+    #[inline(always)]
+    fn referrer_ata(&self) -> Result<&'a AccountInfo, ProgramError> {
+        self.remaining
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+
+    /// The trailing account at `key`, for accounts the swap can derive.
+    #[inline(always)]
+    fn trailing(&self, key: &Pubkey) -> Result<&'a AccountInfo, ProgramError> {
+        self.remaining
+            .iter()
+            .find(|account| account.key() == key)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for SwapAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, user_x_ata, user_y_ata, vault_x, vault_y, config, token_program, ..] = accounts
+        let [user, mint_lp, user_x_ata, user_y_ata, vault_x, vault_y, config, token_program, remaining @ ..] =
+            accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
         Ok(Self {
             user,
+            mint_lp,
             user_x_ata,
             user_y_ata,
             vault_x,
             vault_y,
             config,
             token_program,
+            remaining,
         })
     }
 }
 
 // ─── Instruction Data ───────────────────────────────────────────────────────
 
+/// Fixed-point scale of `oracle_price`, quoted as Y per X.
+pub const ORACLE_PRICE_SCALE: u128 = 1_000_000_000;
+
 #[repr(C, packed)]
 pub struct SwapInstructionData {
     pub is_x: u8,
     pub amount: u64,
     pub min: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
     pub expiration: i64,
+    pub oracle_price: u64,
+    pub max_divergence_bps: u16,
+    /// Share of the swap fee, in basis points and capped by the pool's
+    /// `max_referral_bps`, paid to the referrer's ATA. The fee is charged in
+    /// the input token; the cut is paid out of the output vault at the
+    /// trade's execution price instead. Zero skips the referral and needs no
+    /// referrer.
+    pub referral_bps: u16,
+    /// Zero for exact-output swaps: `amount` is then the output wanted and
+    /// `min` the most input the user will pay.
+    pub is_exact_in: u8,
+    /// Last slot the swap may land in, checked alongside `expiration`; zero
+    /// leaves only the timestamp deadline.
+    pub expiration_slot: u64,
 }
 
 impl<'a> TryFrom<&'a [u8]> for SwapInstructionData {
     type Error = ProgramError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<Self>() {
+        const SWAP_DATA_LEN_V5: usize = size_of::<SwapInstructionData>();
+        const SWAP_DATA_LEN_V4: usize = SWAP_DATA_LEN_V5 - size_of::<u64>();
+        const SWAP_DATA_LEN_V3: usize = SWAP_DATA_LEN_V4 - size_of::<u8>();
+        const SWAP_DATA_LEN_V2: usize = SWAP_DATA_LEN_V3 - size_of::<u16>();
+        const SWAP_DATA_LEN_V1: usize = SWAP_DATA_LEN_V2 - size_of::<u64>() - size_of::<u16>();
+
+        // v2 appends the oracle bound, v3 the referral cut, v4 the swap mode
+        // and v5 the slot deadline; fields an older layout lacks are zeroed,
+        // which disables them
+        let (version, data) = split_version(data, INSTRUCTION_DATA_V5)?;
+        let len = match version {
+            INSTRUCTION_DATA_V1 => SWAP_DATA_LEN_V1,
+            INSTRUCTION_DATA_V2 => SWAP_DATA_LEN_V2,
+            INSTRUCTION_DATA_V3 => SWAP_DATA_LEN_V3,
+            INSTRUCTION_DATA_V4 => SWAP_DATA_LEN_V4,
+            _ => SWAP_DATA_LEN_V5,
+        };
+        if data.len() != len {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        let mut result = {
+            let mut raw: MaybeUninit<[u8; SWAP_DATA_LEN_V5]> = MaybeUninit::uninit();
+            let raw_ptr = raw.as_mut_ptr() as *mut u8;
+            unsafe {
+                core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, len);
+                core::ptr::write_bytes(raw_ptr.add(len), 0, SWAP_DATA_LEN_V5 - len);
+                (raw.as_ptr() as *const Self).read_unaligned()
+            }
+        };
+        // Layouts before v4 are always exact-input
+        if version < INSTRUCTION_DATA_V4 {
+            result.is_exact_in = 1;
+        }
         if result.amount == 0 || result.min == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let clock = Clock::get()?;
-        if clock.unix_timestamp > result.expiration {
+        check_expiration(result.expiration)?;
+        // Both deadlines must hold, so clock drift can't stretch the window
+        if result.expiration_slot != 0 && Clock::get()?.slot > result.expiration_slot {
             return Err(ProgramError::InvalidInstructionData);
         }
         Ok(result)
@@ -75,6 +159,19 @@ impl<'a> TryFrom<&'a [u8]> for SwapInstructionData {
 
 // ─── Instruction ────────────────────────────────────────────────────────────
 
+/// Trades X for Y or Y for X along the constant-product curve.
+///
+/// The LP fee is never transferred anywhere: the curve prices the output off
+/// the input net of `fee`, yet the full input lands in the vault. The fee
+/// portion stays in the reserves, so `k = x * y` grows by it on every swap
+/// and LPs collect it on withdraw. Fee splits must keep this shape: the
+/// protocol and referral cuts are carved out of `swap_result.fee` after the
+/// trade, and anything not carved out remains with LPs.
+///
+/// A wrapped SOL side moves as lamports: native input is wrapped into the
+/// vault from the user's balance, with the System Program passed among the
+/// trailing accounts, and native output is paid to the user's wrapped SOL
+/// ATA and then closed back to the user, unwrapping all of it.
 pub struct Swap<'a> {
     pub accounts: SwapAccounts<'a>,
     pub instruction_data: SwapInstructionData,
@@ -96,12 +193,20 @@ impl<'a> Swap<'a> {
     pub const DISCRIMINATOR: &'a u8 = &3;
 
     pub fn process(&mut self) -> ProgramResult {
-        let config = unsafe { Config::load(self.accounts.config)? };
+        // The config signs for the vaults, so it can never act as the user
+        if self.accounts.user.key() == self.accounts.config.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+        config.check_no_flash_loan()?;
+        config.check_curve()?;
         let is_x = self.instruction_data.is_x != 0;
 
         // Validate AMM state
         if config.state() != AmmState::Initialized as u8 {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(AmmError::InvalidState.into());
         }
 
         // Check vault derivations
@@ -114,7 +219,7 @@ impl<'a> Swap<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_x.ne(self.accounts.vault_x.key()) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(AmmError::InvalidVault.into());
         }
 
         let (vault_y, _) = find_program_address(
@@ -126,37 +231,179 @@ impl<'a> Swap<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(AmmError::InvalidVault.into());
+        }
+
+        // Check mint_lp derivation
+        let mint_lp = create_program_address(
+            &[b"mint_lp", self.accounts.config.key(), &config.lp_bump()],
+            &crate::ID,
+        )?;
+        if mint_lp.ne(self.accounts.mint_lp.key()) {
             return Err(ProgramError::InvalidAccountData);
         }
 
         // Deserialize token accounts
+        let mint_lp = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? };
         let vault_x_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
         let vault_y_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
 
-        // Swap calculations
-        let mut curve = ConstantProduct::init(
+        // Reject pools left in an impossible state
+        validate_reserves(
             vault_x_account.amount(),
             vault_y_account.amount(),
+            Some(mint_lp.supply()),
+        )?;
+
+        // Credit the time since the last update to the pre-trade price
+        config.update_price_cumulatives(
             vault_x_account.amount(),
+            vault_y_account.amount(),
+            Clock::get()?.unix_timestamp,
+        );
+
+        // Compliance pools only deliver allowlisted output mints
+        if let Some(allowlist) = config.has_allowlist() {
+            let output_mint = match is_x {
+                true => config.mint_y(),
+                false => config.mint_x(),
+            };
+            Allowlist::check(self.accounts.trailing(allowlist)?, allowlist, output_mint)?;
+        }
+
+        // Exact-output swaps are sized to the input that yields `amount`, with
+        // `min` capping that input
+        let (amount_in, min_out) = match self.instruction_data.is_exact_in != 0 {
+            true => (self.instruction_data.amount, self.instruction_data.min),
+            false => {
+                let (reserve_in, reserve_out) = match is_x {
+                    true => (vault_x_account.amount(), vault_y_account.amount()),
+                    false => (vault_y_account.amount(), vault_x_account.amount()),
+                };
+                let amount_in = exact_out_input(
+                    reserve_in,
+                    reserve_out,
+                    self.instruction_data.amount,
+                    config.fee(),
+                )?;
+                if amount_in > self.instruction_data.min {
+                    return Err(AmmError::SlippageExceeded.into());
+                }
+                (amount_in, self.instruction_data.amount)
+            }
+        };
+
+        // Snipers can't take more than a sliver of a freshly launched pool
+        if config.in_launch_window(Clock::get()?.unix_timestamp) {
+            let reserve_in = match is_x {
+                true => vault_x_account.amount(),
+                false => vault_y_account.amount(),
+            };
+            let max_input = (reserve_in as u128) * (config.launch_max_input_bps() as u128) / 10_000;
+            if (amount_in as u128) > max_input {
+                return Err(AmmError::LaunchProtection.into());
+            }
+        }
+
+        // Reject the swap if the pool's spot price strays too far from the oracle
+        if self.instruction_data.oracle_price != 0 {
+            check_oracle_divergence(
+                vault_x_account.amount(),
+                vault_y_account.amount(),
+                self.instruction_data.oracle_price,
+                self.instruction_data.max_divergence_bps,
+            )?;
+        }
+
+        if self.instruction_data.referral_bps > config.max_referral_bps() {
+            return Err(AmmError::ReferralTooHigh.into());
+        }
+
+        // Swap calculations. `withdraw` is priced on the input net of the fee,
+        // while the whole `deposit` goes to the vault
+        let mut curve = ConstantProduct::init(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            mint_lp.supply(),
             config.fee(),
             None,
         )
-        .map_err(|_| ProgramError::Custom(1))?;
+        .map_err(|_| AmmError::CurveInit)?;
 
         let p = match is_x {
             true => LiquidityPair::X,
             false => LiquidityPair::Y,
         };
 
-        let swap_result = curve
-            .swap(p, self.instruction_data.amount, self.instruction_data.min)
-            .map_err(|_| ProgramError::Custom(1))?;
+        let swap_result = curve.swap(p, amount_in, min_out).map_err(|e| match e {
+            CurveError::SlippageLimitExceeded => AmmError::SlippageExceeded,
+            _ => AmmError::SwapFailed,
+        })?;
 
         if swap_result.deposit == 0 || swap_result.withdraw == 0 {
-            return Err(ProgramError::InvalidArgument);
+            return Err(AmmError::ZeroTrade.into());
+        }
+
+        // The protocol and referral cuts both come out of the fee, which is
+        // charged in the input token. The referral is then valued in the
+        // output token at the price the trade executed at
+        let protocol_cut = swap_result.fee as u128 * config.protocol_fee() as u128 / 10_000;
+        let referral =
+            swap_result.fee as u128 * self.instruction_data.referral_bps as u128 / 10_000;
+        if protocol_cut + referral > swap_result.fee as u128 {
+            return Err(AmmError::FeeSplitExceeded.into());
         }
+        let referral_out =
+            (referral * swap_result.withdraw as u128 / swap_result.deposit as u128) as u64;
+        let output_out = swap_result
+            .withdraw
+            .checked_add(referral_out)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Keep both vaults at or above the pool's reserve floors
+        let (post_x, post_y) = match is_x {
+            true => (
+                vault_x_account.amount().saturating_add(swap_result.deposit),
+                vault_y_account.amount().saturating_sub(output_out),
+            ),
+            false => (
+                vault_x_account.amount().saturating_sub(output_out),
+                vault_y_account.amount().saturating_add(swap_result.deposit),
+            ),
+        };
+        if post_x < config.min_reserve_x() || post_y < config.min_reserve_y() {
+            return Err(AmmError::ReserveBelowFloor.into());
+        }
+
+        // Cap how far one swap can push the pool's price
+        let max_price_impact_bps = config.max_price_impact_bps();
+        if max_price_impact_bps != 0 {
+            let impact = match is_x {
+                true => price_impact_bps(
+                    vault_x_account.amount(),
+                    vault_y_account.amount(),
+                    post_x,
+                    post_y,
+                ),
+                false => price_impact_bps(
+                    vault_y_account.amount(),
+                    vault_x_account.amount(),
+                    post_y,
+                    post_x,
+                ),
+            };
+            if impact > max_price_impact_bps as u64 {
+                return Err(AmmError::SlippageExceeded.into());
+            }
+        }
+
+        // Output vault balance before the transfers, to check what actually moved
+        let (output_vault, output_before) = match is_x {
+            true => (self.accounts.vault_y, vault_y_account.amount()),
+            false => (self.accounts.vault_x, vault_x_account.amount()),
+        };
 
         // Build config signer seeds
         let seed_binding = config.seed().to_le_bytes();
@@ -170,16 +417,38 @@ impl<'a> Swap<'a> {
         ];
         let signer = Signer::from(&config_seeds);
 
+        // Wrapping native SOL input invokes the System Program, which swap
+        // doesn't otherwise take, so it has to come among the trailing accounts
+        let native_in = match is_x {
+            true => config.is_native_x(),
+            false => config.is_native_y(),
+        };
+        if native_in {
+            self.accounts.trailing(&pinocchio_system::ID)?;
+        }
+
+        // Pay the referrer its cut of the LP fee out of the output vault, so
+        // the user's cost and output are unchanged
+        if referral_out != 0 {
+            Transfer {
+                from: output_vault,
+                to: self.accounts.referrer_ata()?,
+                authority: self.accounts.config,
+                amount: referral_out,
+            }
+            .invoke_signed(&[signer.clone()])?;
+        }
+
         // Execute transfers based on swap direction
         if is_x {
             // User sends X to vault, receives Y from vault
-            Transfer {
-                from: self.accounts.user_x_ata,
-                to: self.accounts.vault_x,
-                authority: self.accounts.user,
-                amount: swap_result.deposit,
-            }
-            .invoke()?;
+            NativeSol::transfer_in(
+                native_in,
+                self.accounts.user,
+                self.accounts.user_x_ata,
+                self.accounts.vault_x,
+                swap_result.deposit,
+            )?;
 
             Transfer {
                 from: self.accounts.vault_y,
@@ -187,16 +456,21 @@ impl<'a> Swap<'a> {
                 authority: self.accounts.config,
                 amount: swap_result.withdraw,
             }
-            .invoke_signed(&[signer])?;
+            .invoke_signed(&[signer.clone()])?;
+            NativeSol::unwrap(
+                config.is_native_y(),
+                self.accounts.user,
+                self.accounts.user_y_ata,
+            )?;
         } else {
             // User sends Y to vault, receives X from vault
-            Transfer {
-                from: self.accounts.user_y_ata,
-                to: self.accounts.vault_y,
-                authority: self.accounts.user,
-                amount: swap_result.deposit,
-            }
-            .invoke()?;
+            NativeSol::transfer_in(
+                native_in,
+                self.accounts.user,
+                self.accounts.user_y_ata,
+                self.accounts.vault_y,
+                swap_result.deposit,
+            )?;
 
             Transfer {
                 from: self.accounts.vault_x,
@@ -204,9 +478,137 @@ impl<'a> Swap<'a> {
                 authority: self.accounts.config,
                 amount: swap_result.withdraw,
             }
+            .invoke_signed(&[signer.clone()])?;
+            NativeSol::unwrap(
+                config.is_native_x(),
+                self.accounts.user,
+                self.accounts.user_x_ata,
+            )?;
+        }
+
+        // Skim the protocol's share of the fee out of the input vault into the
+        // fee authority's vault, where only `CollectProtocolFees` can move it
+        if protocol_cut != 0 {
+            let (input_vault, input_mint) = match is_x {
+                true => (self.accounts.vault_x, config.mint_x()),
+                false => (self.accounts.vault_y, config.mint_y()),
+            };
+            let (fee_authority, _) = find_fee_authority(self.accounts.config.key());
+            let (fee_vault, _) = find_program_address(
+                &[
+                    &fee_authority,
+                    self.accounts.token_program.key(),
+                    input_mint,
+                ],
+                &pinocchio_associated_token_account::ID,
+            );
+            Transfer {
+                from: input_vault,
+                to: self.accounts.trailing(&fee_vault)?,
+                authority: self.accounts.config,
+                amount: protocol_cut as u64,
+            }
             .invoke_signed(&[signer])?;
         }
 
+        // A Token-2022 hook moving a different amount would break the min guarantee
+        let output_after =
+            unsafe { TokenAccount::from_account_info_unchecked(output_vault)? }.amount();
+        if output_before.checked_sub(output_after) != Some(output_out) {
+            return Err(AmmError::TransferMismatch.into());
+        }
+
+        config.increment_swap_count();
+
+        // Receipt for frontends: input, output, fee and the effective
+        // output-per-input price scaled by ORACLE_PRICE_SCALE
+        let effective_price =
+            (swap_result.withdraw as u128) * ORACLE_PRICE_SCALE / (swap_result.deposit as u128);
+        sol_log_data(&[
+            b"swap",
+            &swap_result.deposit.to_le_bytes(),
+            &swap_result.withdraw.to_le_bytes(),
+            &swap_result.fee.to_le_bytes(),
+            &effective_price.to_le_bytes(),
+        ]);
+
+        // Indexer record with the reserves the trade left behind
+        let (reserve_x, reserve_y) = unsafe {
+            (
+                TokenAccount::from_account_info_unchecked(self.accounts.vault_x)?.amount(),
+                TokenAccount::from_account_info_unchecked(self.accounts.vault_y)?.amount(),
+            )
+        };
+        sol_log_data(&[
+            b"swap_reserves",
+            &encode_reserves_event(
+                is_x,
+                swap_result.deposit,
+                swap_result.withdraw,
+                reserve_x,
+                reserve_y,
+            ),
+        ]);
+
         Ok(())
     }
 }
+
+/// How far, in basis points, a swap moving the reserves from
+/// `reserve_in`/`reserve_out` to `post_in`/`post_out` drops the spot price of
+/// the input token. The drop is the product of two ratios no greater than
+/// one, each taken in Q64.64 so the result never overflows, and is rounded
+/// up so the cap errs towards rejecting.
+#[inline(always)]
+pub fn price_impact_bps(reserve_in: u64, reserve_out: u64, post_in: u64, post_out: u64) -> u64 {
+    if reserve_out == 0 || post_in == 0 {
+        return 0;
+    }
+    let out_ratio = ((post_out.min(reserve_out) as u128) << 64) / reserve_out as u128;
+    let price_ratio = out_ratio * reserve_in.min(post_in) as u128 / post_in as u128;
+    10_000 - ((price_ratio * 10_000) >> 64) as u64
+}
+
+/// Fails if the spot price `y / x` diverges from `oracle_price` (scaled by
+/// `ORACLE_PRICE_SCALE`) by more than `max_divergence_bps`. An empty pool
+/// has no spot price, so it never agrees with the oracle.
+#[inline(always)]
+pub fn check_oracle_divergence(
+    x: u64,
+    y: u64,
+    oracle_price: u64,
+    max_divergence_bps: u16,
+) -> ProgramResult {
+    if x == 0 {
+        return Err(AmmError::OracleDivergence.into());
+    }
+    let spot_price = (y as u128)
+        .checked_mul(ORACLE_PRICE_SCALE)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / x as u128;
+    let oracle_price = oracle_price as u128;
+    let divergence_bps = spot_price.abs_diff(oracle_price) * 10_000 / oracle_price;
+    if divergence_bps > max_divergence_bps as u128 {
+        return Err(AmmError::OracleDivergence.into());
+    }
+    Ok(())
+}
+
+/// Smallest input, fee included, that buys `amount_out` from a pool holding
+/// `reserve_in`/`reserve_out` at `fee` basis points. Rounds up so the curve
+/// always delivers at least `amount_out`.
+#[inline(always)]
+pub fn exact_out_input(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_out: u64,
+    fee: u16,
+) -> Result<u64, ProgramError> {
+    if amount_out >= reserve_out || fee >= 10_000 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let net_in =
+        (reserve_in as u128 * amount_out as u128).div_ceil((reserve_out - amount_out) as u128);
+    let gross_in = (net_in * 10_000).div_ceil((10_000 - fee) as u128);
+    u64::try_from(gross_in).map_err(|_| ProgramError::ArithmeticOverflow)
+}
@@ -0,0 +1,98 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_token::state::TokenAccount;
+
+use crate::instructions::{Swap, SwapAccounts, SwapInstructionData};
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct SwapAndPayAccounts<'a> {
+    pub customer: &'a AccountInfo,
+    pub customer_ata: &'a AccountInfo,
+    pub merchant: &'a AccountInfo,
+    pub merchant_ata: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapAndPayAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [customer, customer_ata, merchant, merchant_ata, mint_lp, vault_x, vault_y, config, token_program, remaining @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // The output must land in an account the merchant owns
+        if TokenAccount::from_account_info(merchant_ata)?.owner() != merchant.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            customer,
+            customer_ata,
+            merchant,
+            merchant_ata,
+            mint_lp,
+            vault_x,
+            vault_y,
+            config,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Checkout flow: the customer pays `amount` of the input token, the pool
+/// swaps it and the merchant receives at least `min` of the output token.
+/// Takes the same instruction data as `Swap`.
+pub struct SwapAndPay<'a> {
+    pub accounts: SwapAndPayAccounts<'a>,
+    pub instruction_data: SwapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SwapAndPay<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapAndPayAccounts::try_from(accounts)?;
+        let instruction_data = SwapInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SwapAndPay<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(self) -> ProgramResult {
+        // Route the swap output to the merchant instead of back to the customer
+        let (user_x_ata, user_y_ata) = match self.instruction_data.is_x != 0 {
+            true => (self.accounts.customer_ata, self.accounts.merchant_ata),
+            false => (self.accounts.merchant_ata, self.accounts.customer_ata),
+        };
+
+        Swap {
+            accounts: SwapAccounts {
+                user: self.accounts.customer,
+                mint_lp: self.accounts.mint_lp,
+                user_x_ata,
+                user_y_ata,
+                vault_x: self.accounts.vault_x,
+                vault_y: self.accounts.vault_y,
+                config: self.accounts.config,
+                token_program: self.accounts.token_program,
+                remaining: self.accounts.remaining,
+            },
+            instruction_data: self.instruction_data,
+        }
+        .process()
+    }
+}
@@ -0,0 +1,194 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    errors::AmmError,
+    instructions::{Swap, SwapAccounts, SwapInstructionData},
+    state::Config,
+};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct SwapSplitAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub user_x_ata: &'a AccountInfo,
+    pub user_y_ata: &'a AccountInfo,
+    pub mint_lp_a: &'a AccountInfo,
+    pub vault_x_a: &'a AccountInfo,
+    pub vault_y_a: &'a AccountInfo,
+    pub config_a: &'a AccountInfo,
+    pub mint_lp_b: &'a AccountInfo,
+    pub vault_x_b: &'a AccountInfo,
+    pub vault_y_b: &'a AccountInfo,
+    pub config_b: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// Protocol fee vaults for either pool, matched by address.
+    pub remaining: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapSplitAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, user_x_ata, user_y_ata, mint_lp_a, vault_x_a, vault_y_a, config_a, mint_lp_b, vault_x_b, vault_y_b, config_b, token_program, remaining @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if config_a.key() == config_b.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Self {
+            user,
+            user_x_ata,
+            user_y_ata,
+            mint_lp_a,
+            vault_x_a,
+            vault_y_a,
+            config_a,
+            mint_lp_b,
+            vault_x_b,
+            vault_y_b,
+            config_b,
+            token_program,
+            remaining,
+        })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct SwapSplitInstructionData {
+    pub is_x: u8,
+    pub amount: u64,
+    /// Share of `amount` routed through pool A, in basis points.
+    pub split_bps: u16,
+    pub min: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
+    pub expiration: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwapSplitInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        if result.amount == 0 || result.min == 0 || result.split_bps > 10_000 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        check_expiration(result.expiration)?;
+        Ok(result)
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Splits one swap across two pools of the same pair (e.g. two fee tiers),
+/// enforcing `min` on the combined output. Pools with an output allowlist are
+/// not supported.
+pub struct SwapSplit<'a> {
+    pub accounts: SwapSplitAccounts<'a>,
+    pub instruction_data: SwapSplitInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SwapSplit<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapSplitAccounts::try_from(accounts)?;
+        let instruction_data = SwapSplitInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SwapSplit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Both pools must trade the same pair
+        {
+            let config_a = unsafe { Config::load(self.accounts.config_a)? };
+            let config_b = unsafe { Config::load(self.accounts.config_b)? };
+            if config_a.mint_x() != config_b.mint_x() || config_a.mint_y() != config_b.mint_y() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        let is_x = self.instruction_data.is_x;
+        let amount = self.instruction_data.amount;
+        let amount_a = (amount as u128 * self.instruction_data.split_bps as u128 / 10_000) as u64;
+        let amount_b = amount - amount_a;
+
+        let user_out_ata = match is_x != 0 {
+            true => self.accounts.user_y_ata,
+            false => self.accounts.user_x_ata,
+        };
+        let balance_before =
+            unsafe { TokenAccount::from_account_info_unchecked(user_out_ata)? }.amount();
+
+        // Each leg only has to produce something, `min` applies to the total
+        for (leg_amount, mint_lp, vault_x, vault_y, config) in [
+            (
+                amount_a,
+                self.accounts.mint_lp_a,
+                self.accounts.vault_x_a,
+                self.accounts.vault_y_a,
+                self.accounts.config_a,
+            ),
+            (
+                amount_b,
+                self.accounts.mint_lp_b,
+                self.accounts.vault_x_b,
+                self.accounts.vault_y_b,
+                self.accounts.config_b,
+            ),
+        ] {
+            if leg_amount == 0 {
+                continue;
+            }
+            Swap {
+                accounts: SwapAccounts {
+                    user: self.accounts.user,
+                    mint_lp,
+                    user_x_ata: self.accounts.user_x_ata,
+                    user_y_ata: self.accounts.user_y_ata,
+                    vault_x,
+                    vault_y,
+                    config,
+                    token_program: self.accounts.token_program,
+                    remaining: self.accounts.remaining,
+                },
+                instruction_data: SwapInstructionData {
+                    is_x,
+                    amount: leg_amount,
+                    min: 1,
+                    expiration: self.instruction_data.expiration,
+                    oracle_price: 0,
+                    max_divergence_bps: 0,
+                    referral_bps: 0,
+                    is_exact_in: 1,
+                    expiration_slot: 0,
+                },
+            }
+            .process()?;
+        }
+
+        // Check for slippage on the combined output
+        let balance_after =
+            unsafe { TokenAccount::from_account_info_unchecked(user_out_ata)? }.amount();
+        if balance_after.saturating_sub(balance_before) < self.instruction_data.min {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Ok(())
+    }
+}
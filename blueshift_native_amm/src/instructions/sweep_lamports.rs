@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::state::Config;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct SweepLamportsAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SweepLamportsAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Sends any lamports held by the config above its rent-exempt minimum to the
+/// authority, leaving the account open.
+pub struct SweepLamports<'a> {
+    pub accounts: SweepLamportsAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SweepLamports<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SweepLamportsAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SweepLamports<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can sweep, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        let rent = Rent::get()?;
+        let excess = self
+            .accounts
+            .config
+            .lamports()
+            .saturating_sub(rent.minimum_balance(Config::LEN));
+        if excess == 0 {
+            return Ok(());
+        }
+
+        *self.accounts.config.try_borrow_mut_lamports()? -= excess;
+        *self.accounts.authority.try_borrow_mut_lamports()? += excess;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use core::mem::size_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::state::Config;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct UpdateAuthorityAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateAuthorityAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct UpdateAuthorityInstructionData {
+    pub new_authority: [u8; 32],
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateAuthorityInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Hands pool governance to a new authority key. An all-zero key renounces
+/// control, leaving the pool immutable.
+pub struct UpdateAuthority<'a> {
+    pub accounts: UpdateAuthorityAccounts<'a>,
+    pub instruction_data: UpdateAuthorityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateAuthority<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateAuthorityAccounts::try_from(accounts)?;
+        let instruction_data = UpdateAuthorityInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can rotate itself, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        config.set_authority(self.instruction_data.new_authority);
+
+        Ok(())
+    }
+}
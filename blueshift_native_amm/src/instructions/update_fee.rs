@@ -0,0 +1,95 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log_data, program_error::ProgramError, ProgramResult,
+};
+
+use crate::state::{AmmState, Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct UpdateFeeAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateFeeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct UpdateFeeInstructionData {
+    pub fee: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateFeeInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Changes the pool's swap fee and logs the old and new values so indexers
+/// can track fee history.
+pub struct UpdateFee<'a> {
+    pub accounts: UpdateFeeAccounts<'a>,
+    pub instruction_data: UpdateFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateFee<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateFeeAccounts::try_from(accounts)?;
+        let instruction_data = UpdateFeeInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateFee<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can change the fee, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        if config.state() == AmmState::Disabled as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let old_fee = config.fee();
+        config.set_fee(self.instruction_data.fee)?;
+
+        sol_log_data(&[
+            b"fee_update",
+            &old_fee.to_le_bytes(),
+            &config.fee().to_le_bytes(),
+        ]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use core::mem::size_of;
+
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log_data, program_error::ProgramError, ProgramResult,
+};
+
+use crate::state::{AmmState, Config};
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct UpdateProtocolFeeAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateProtocolFeeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+#[repr(C, packed)]
+pub struct UpdateProtocolFeeInstructionData {
+    pub protocol_fee: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateProtocolFeeInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (_, data) = split_version(data, INSTRUCTION_DATA_V1)?;
+        if data.len() != size_of::<Self>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Changes the share of each swap fee skimmed to the protocol, logging the
+/// old and new values like `UpdateFee`.
+pub struct UpdateProtocolFee<'a> {
+    pub accounts: UpdateProtocolFeeAccounts<'a>,
+    pub instruction_data: UpdateProtocolFeeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateProtocolFee<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateProtocolFeeAccounts::try_from(accounts)?;
+        let instruction_data = UpdateProtocolFeeInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateProtocolFee<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can change the protocol fee
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        if config.state() == AmmState::Disabled as u8 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let old_protocol_fee = config.protocol_fee();
+        config.set_protocol_fee(self.instruction_data.protocol_fee)?;
+
+        sol_log_data(&[
+            b"protocol_fee_update",
+            &old_protocol_fee.to_le_bytes(),
+            &config.protocol_fee().to_le_bytes(),
+        ]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use core::mem::{size_of, MaybeUninit};
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::state::Config;
+
+use super::helpers::*;
+
+// ─── Accounts ───────────────────────────────────────────────────────────────
+
+pub struct UpdateRiskParamsAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateRiskParamsAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Self { authority, config })
+    }
+}
+
+// ─── Instruction Data ───────────────────────────────────────────────────────
+
+/// Each field set to its type's `MAX` leaves the stored value unchanged.
+#[repr(C, packed)]
+pub struct UpdateRiskParamsInstructionData {
+    pub min_lp_mint: u64,
+    pub min_reserve_x: u64,
+    pub min_reserve_y: u64,
+    pub launch_window: i64,
+    pub launch_max_input_bps: u16,
+    pub max_referral_bps: u16,
+    pub flash_fee: u16,
+    pub max_price_impact_bps: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateRiskParamsInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        const RISK_DATA_LEN_V3: usize = size_of::<UpdateRiskParamsInstructionData>();
+        const RISK_DATA_LEN_V2: usize = RISK_DATA_LEN_V3 - size_of::<u16>();
+        const RISK_DATA_LEN_V1: usize = RISK_DATA_LEN_V2 - size_of::<u16>();
+
+        // v2 appends the flash fee and v3 the price impact cap; fields an
+        // older layout lacks are left unchanged
+        let (version, data) = split_version(data, INSTRUCTION_DATA_V3)?;
+        let len = match version {
+            INSTRUCTION_DATA_V1 => RISK_DATA_LEN_V1,
+            INSTRUCTION_DATA_V2 => RISK_DATA_LEN_V2,
+            _ => RISK_DATA_LEN_V3,
+        };
+        if data.len() != len {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let mut raw: MaybeUninit<[u8; RISK_DATA_LEN_V3]> = MaybeUninit::uninit();
+        let raw_ptr = raw.as_mut_ptr() as *mut u8;
+        unsafe {
+            core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, len);
+            core::ptr::write_bytes(raw_ptr.add(len), 0xff, RISK_DATA_LEN_V3 - len);
+            Ok((raw.as_ptr() as *const Self).read_unaligned())
+        }
+    }
+}
+
+// ─── Instruction ────────────────────────────────────────────────────────────
+
+/// Updates any subset of the pool's risk parameters in one go, so related
+/// limits never apply half-changed.
+pub struct UpdateRiskParams<'a> {
+    pub accounts: UpdateRiskParamsAccounts<'a>,
+    pub instruction_data: UpdateRiskParamsInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for UpdateRiskParams<'a> {
+    type Error = ProgramError;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateRiskParamsAccounts::try_from(accounts)?;
+        let instruction_data = UpdateRiskParamsInstructionData::try_from(data)?;
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateRiskParams<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+
+        // Only the pool authority can change limits, and an immutable pool has none
+        AuthorityAccount::check(self.accounts.authority, config)?;
+
+        let params = &self.instruction_data;
+
+        if params.min_lp_mint != u64::MAX {
+            config.set_min_lp_mint(params.min_lp_mint);
+        }
+        if params.min_reserve_x != u64::MAX {
+            config.set_min_reserve_x(params.min_reserve_x);
+        }
+        if params.min_reserve_y != u64::MAX {
+            config.set_min_reserve_y(params.min_reserve_y);
+        }
+
+        // The launch window and its cap are validated together
+        let launch_window = match params.launch_window {
+            i64::MAX => config.launch_window(),
+            launch_window => launch_window,
+        };
+        let launch_max_input_bps = match params.launch_max_input_bps {
+            u16::MAX => config.launch_max_input_bps(),
+            launch_max_input_bps => launch_max_input_bps,
+        };
+        config.set_launch_protection(launch_window, launch_max_input_bps)?;
+
+        if params.max_referral_bps != u16::MAX {
+            config.set_max_referral_bps(params.max_referral_bps)?;
+        }
+        if params.flash_fee != u16::MAX {
+            config.set_flash_fee(params.flash_fee)?;
+        }
+        if params.max_price_impact_bps != u16::MAX {
+            config.set_max_price_impact_bps(params.max_price_impact_bps)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,21 +1,23 @@
-use core::mem::size_of;
+use core::mem::{size_of, MaybeUninit};
 
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::{create_program_address, find_program_address},
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::{
-    instructions::{Burn, Transfer},
-    state::{Mint, TokenAccount},
-};
+use pinocchio_token::{instructions::Transfer, state::TokenAccount};
 
 use constant_product_curve::ConstantProduct;
 
-use crate::state::{AmmState, Config};
+use crate::{
+    errors::AmmError,
+    state::{AmmState, Config, Position},
+};
+
+use super::helpers::*;
 
 // ─── Accounts ───────────────────────────────────────────────────────────────
 
@@ -29,12 +31,14 @@ pub struct WithdrawAccounts<'a> {
     pub user_lp_ata: &'a AccountInfo,
     pub config: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    /// The user's `Position`, updated when supplied.
+    pub position: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, mint_lp, vault_x, vault_y, user_x_ata, user_y_ata, user_lp_ata, config, token_program, ..] =
+        let [user, mint_lp, vault_x, vault_y, user_x_ata, user_y_ata, user_lp_ata, config, token_program, remaining @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -49,6 +53,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             user_lp_ata,
             config,
             token_program,
+            // Soulbound pools trail Token-2022 for the LP burn, which is
+            // never mistaken for a position
+            position: remaining
+                .first()
+                .filter(|account| account.is_owned_by(&crate::ID)),
         })
     }
 }
@@ -60,29 +69,45 @@ pub struct WithdrawInstructionData {
     pub amount: u64,
     pub min_x: u64,
     pub min_y: u64,
+    /// Unix timestamp the transaction must land by; zero sets no deadline.
     pub expiration: i64,
+    pub allow_zero_slippage: u8,
 }
 
 impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
     type Error = ProgramError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<Self>() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-        let result = unsafe { (data.as_ptr() as *const Self).read_unaligned() };
+        const WITHDRAW_DATA_LEN_WITH_FLAG: usize = size_of::<WithdrawInstructionData>();
+        const WITHDRAW_DATA_LEN: usize = WITHDRAW_DATA_LEN_WITH_FLAG - size_of::<u8>();
+
+        // v2 appends the allow_zero_slippage flag, which v1 leaves unset
+        let result = match split_version(data, INSTRUCTION_DATA_V2)? {
+            (INSTRUCTION_DATA_V2, data) if data.len() == WITHDRAW_DATA_LEN_WITH_FLAG => unsafe {
+                (data.as_ptr() as *const Self).read_unaligned()
+            },
+            (INSTRUCTION_DATA_V1, data) if data.len() == WITHDRAW_DATA_LEN => {
+                let mut raw: MaybeUninit<[u8; WITHDRAW_DATA_LEN_WITH_FLAG]> = MaybeUninit::uninit();
+                let raw_ptr = raw.as_mut_ptr() as *mut u8;
+                unsafe {
+                    core::ptr::copy_nonoverlapping(data.as_ptr(), raw_ptr, WITHDRAW_DATA_LEN);
+                    core::ptr::write_bytes(raw_ptr.add(WITHDRAW_DATA_LEN), 0, 1);
+                    (raw.as_ptr() as *const Self).read_unaligned()
+                }
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
         if result.amount == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
-        let clock = Clock::get()?;
-        if clock.unix_timestamp > result.expiration {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        check_expiration(result.expiration)?;
         Ok(result)
     }
 }
 
 // ─── Instruction ────────────────────────────────────────────────────────────
 
+/// A wrapped SOL side is paid to the user's wrapped SOL ATA and then closed
+/// back to the user, so it arrives as lamports.
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
     pub instruction_data: WithdrawInstructionData,
@@ -104,13 +129,21 @@ impl<'a> Withdraw<'a> {
     pub const DISCRIMINATOR: &'a u8 = &2;
 
     pub fn process(&mut self) -> ProgramResult {
-        let config = unsafe { Config::load(self.accounts.config)? };
+        // The config signs for the vaults, so it can never act as the user
+        if self.accounts.user.key() == self.accounts.config.key() {
+            return Err(ProgramError::InvalidArgument);
+        }
 
-        // Validate AMM state (allow Initialized and WithdrawOnly, reject Disabled)
-        if config.state() == AmmState::Disabled as u8
-            || config.state() == AmmState::Uninitialized as u8
-        {
-            return Err(ProgramError::InvalidAccountData);
+        let config = unsafe { Config::load_mut(self.accounts.config)? };
+        config.check_initialized()?;
+        config.check_no_flash_loan()?;
+        config.check_curve()?;
+
+        // Validate AMM state (allow Initialized and WithdrawOnly, reject Disabled
+        // once its grace period is over)
+        let state = config.effective_state(Clock::get()?.unix_timestamp);
+        if state == AmmState::Disabled as u8 {
+            return Err(AmmError::InvalidState.into());
         }
 
         // Check vault derivations
@@ -123,7 +156,7 @@ impl<'a> Withdraw<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_x.ne(self.accounts.vault_x.key()) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(AmmError::InvalidVault.into());
         }
 
         let (vault_y, _) = find_program_address(
@@ -135,35 +168,64 @@ impl<'a> Withdraw<'a> {
             &pinocchio_associated_token_account::ID,
         );
         if vault_y.ne(self.accounts.vault_y.key()) {
+            return Err(AmmError::InvalidVault.into());
+        }
+
+        // Check mint_lp derivation
+        let mint_lp = create_program_address(
+            &[b"mint_lp", self.accounts.config.key(), &config.lp_bump()],
+            &crate::ID,
+        )?;
+        if mint_lp.ne(self.accounts.mint_lp.key()) {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        // Deserialize token accounts
-        let mint_lp = unsafe { Mint::from_account_info_unchecked(self.accounts.mint_lp)? };
+        // Deserialize token accounts; the LP mint may belong to SPL Token,
+        // or Token-2022 for soulbound pools
+        let mint_lp = unsafe { LpMint::load_unchecked(self.accounts.mint_lp)? };
         let vault_x_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_x)? };
         let vault_y_account =
             unsafe { TokenAccount::from_account_info_unchecked(self.accounts.vault_y)? };
 
+        // Reject pools left in an impossible state
+        validate_reserves(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Some(mint_lp.supply()),
+        )?;
+
+        // Credit the time since the last update to the pre-trade price
+        config.update_price_cumulatives(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            Clock::get()?.unix_timestamp,
+        );
+
+        // A partial withdraw with no minimums has no slippage protection at
+        // all, so it must be asked for explicitly. Exiting the user's whole
+        // LP balance is exempt; the pool's supply never drains, as the
+        // locked minimum stays behind
+        let user_lp = unsafe { LpMint::account_unchecked(self.accounts.user_lp_ata)? }.amount();
+        if self.instruction_data.min_x == 0
+            && self.instruction_data.min_y == 0
+            && self.instruction_data.amount != user_lp
+            && self.instruction_data.allow_zero_slippage == 0
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
         // Calculate withdrawal amounts
-        let (x, y) = match mint_lp.supply() == self.instruction_data.amount {
-            true => (vault_x_account.amount(), vault_y_account.amount()),
-            false => {
-                let amounts = ConstantProduct::xy_withdraw_amounts_from_l(
-                    vault_x_account.amount(),
-                    vault_y_account.amount(),
-                    mint_lp.supply(),
-                    self.instruction_data.amount,
-                    6,
-                )
-                .map_err(|_| ProgramError::InvalidArgument)?;
-                (amounts.x, amounts.y)
-            }
-        };
+        let (x, y) = withdraw_amounts(
+            vault_x_account.amount(),
+            vault_y_account.amount(),
+            mint_lp.supply(),
+            self.instruction_data.amount,
+        )?;
 
         // Check for slippage
         if !(x >= self.instruction_data.min_x && y >= self.instruction_data.min_y) {
-            return Err(ProgramError::InvalidArgument);
+            return Err(AmmError::SlippageExceeded.into());
         }
 
         // Build config signer seeds
@@ -196,15 +258,57 @@ impl<'a> Withdraw<'a> {
         }
         .invoke_signed(&[signer])?;
 
+        // Hand a native SOL side back as lamports
+        NativeSol::unwrap(
+            config.is_native_x(),
+            self.accounts.user,
+            self.accounts.user_x_ata,
+        )?;
+        NativeSol::unwrap(
+            config.is_native_y(),
+            self.accounts.user,
+            self.accounts.user_y_ata,
+        )?;
+
         // Burn LP tokens from user
-        Burn {
-            account: self.accounts.user_lp_ata,
-            mint: self.accounts.mint_lp,
-            authority: self.accounts.user,
-            amount: self.instruction_data.amount,
+        LpMint::burn(
+            self.accounts.user_lp_ata,
+            self.accounts.mint_lp,
+            self.accounts.user,
+            self.instruction_data.amount,
+            &[],
+        )?;
+
+        // Keep the user's position in sync
+        if let Some(position) = self.accounts.position {
+            let position = unsafe {
+                Position::load_for(
+                    position,
+                    self.accounts.user.key(),
+                    self.accounts.config.key(),
+                )?
+            };
+            position.set_lp_amount(
+                position
+                    .lp_amount()
+                    .saturating_sub(self.instruction_data.amount),
+            );
         }
-        .invoke()?;
 
         Ok(())
     }
 }
+
+/// Reserves released by burning `amount` of `supply` LP, rounded down.
+#[inline(always)]
+pub fn withdraw_amounts(
+    x: u64,
+    y: u64,
+    supply: u64,
+    amount: u64,
+) -> Result<(u64, u64), ProgramError> {
+    let amounts =
+        ConstantProduct::xy_withdraw_amounts_from_l(x, y, supply, amount, CURVE_PRECISION)
+            .map_err(|_| ProgramError::InvalidArgument)?;
+    Ok((amounts.x, amounts.y))
+}
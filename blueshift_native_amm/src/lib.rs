@@ -5,6 +5,9 @@ use pinocchio::{
 
 entrypoint!(process_instruction);
 
+pub mod errors;
+pub use errors::*;
+
 pub mod instructions;
 pub use instructions::*;
 
@@ -30,6 +33,62 @@ fn process_instruction(
         Some((Deposit::DISCRIMINATOR, data)) => Deposit::try_from((data, accounts))?.process(),
         Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
         Some((Swap::DISCRIMINATOR, data)) => Swap::try_from((data, accounts))?.process(),
+        Some((SetState::DISCRIMINATOR, data)) => SetState::try_from((data, accounts))?.process(),
+        Some((Disable::DISCRIMINATOR, data)) => Disable::try_from((data, accounts))?.process(),
+        Some((PreviewDeposit::DISCRIMINATOR, data)) => {
+            PreviewDeposit::try_from((data, accounts))?.process()
+        }
+        Some((SwapAndPay::DISCRIMINATOR, data)) => {
+            SwapAndPay::try_from((data, accounts))?.process()
+        }
+        Some((SweepLamports::DISCRIMINATOR, _)) => SweepLamports::try_from(accounts)?.process(),
+        Some((InitAllowlist::DISCRIMINATOR, data)) => {
+            InitAllowlist::try_from((data, accounts))?.process()
+        }
+        Some((RenounceAuthority::DISCRIMINATOR, data)) => {
+            RenounceAuthority::try_from((data, accounts))?.process()
+        }
+        Some((CollectProtocolFees::DISCRIMINATOR, data)) => {
+            CollectProtocolFees::try_from((data, accounts))?.process()
+        }
+        Some((SwapSplit::DISCRIMINATOR, data)) => SwapSplit::try_from((data, accounts))?.process(),
+        Some((OpenPosition::DISCRIMINATOR, _)) => OpenPosition::try_from(accounts)?.process(),
+        Some((ClosePosition::DISCRIMINATOR, _)) => ClosePosition::try_from(accounts)?.process(),
+        Some((BatchInitialize::DISCRIMINATOR, data)) => {
+            BatchInitialize::try_from((data, accounts))?.process()
+        }
+        Some((UpdateFee::DISCRIMINATOR, data)) => UpdateFee::try_from((data, accounts))?.process(),
+        Some((GetInvariant::DISCRIMINATOR, _)) => GetInvariant::try_from(accounts)?.process(),
+        Some((GetLpLabel::DISCRIMINATOR, _)) => GetLpLabel::try_from(accounts)?.process(),
+        Some((UpdateAuthority::DISCRIMINATOR, data)) => {
+            UpdateAuthority::try_from((data, accounts))?.process()
+        }
+        Some((QuoteDeposit::DISCRIMINATOR, data)) => {
+            QuoteDeposit::try_from((data, accounts))?.process()
+        }
+        Some((UpdateRiskParams::DISCRIMINATOR, data)) => {
+            UpdateRiskParams::try_from((data, accounts))?.process()
+        }
+        Some((UpdateProtocolFee::DISCRIMINATOR, data)) => {
+            UpdateProtocolFee::try_from((data, accounts))?.process()
+        }
+        Some((Quote::DISCRIMINATOR, data)) => Quote::try_from((data, accounts))?.process(),
+        Some((DepositSingle::DISCRIMINATOR, data)) => {
+            DepositSingle::try_from((data, accounts))?.process()
+        }
+        Some((MigrateLiquidity::DISCRIMINATOR, data)) => {
+            MigrateLiquidity::try_from((data, accounts))?.process()
+        }
+        Some((CloseConfig::DISCRIMINATOR, _)) => CloseConfig::try_from(accounts)?.process(),
+        Some((FlashBorrow::DISCRIMINATOR, data)) => {
+            FlashBorrow::try_from((data, accounts))?.process()
+        }
+        Some((FlashRepay::DISCRIMINATOR, _)) => FlashRepay::try_from(accounts)?.process(),
+        Some((Donate::DISCRIMINATOR, data)) => Donate::try_from((data, accounts))?.process(),
+        Some((BeginSunset::DISCRIMINATOR, data)) => {
+            BeginSunset::try_from((data, accounts))?.process()
+        }
+        Some((FinalizeSunset::DISCRIMINATOR, _)) => FinalizeSunset::try_from(accounts)?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
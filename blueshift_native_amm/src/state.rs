@@ -3,6 +3,7 @@ use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::
 
 #[repr(C)]
 pub struct Config {
+    version: u8,
     state: u8,
     seed: [u8; 8],
     authority: Pubkey,
@@ -10,6 +11,31 @@ pub struct Config {
     mint_y: Pubkey,
     fee: [u8; 2],
     config_bump: [u8; 1],
+    disabled_at: [u8; 8],
+    grace_seconds: [u8; 8],
+    min_lp_mint: [u8; 8],
+    allowlist: Pubkey,
+    protocol_fee_recipient: Pubkey,
+    min_reserve_x: [u8; 8],
+    min_reserve_y: [u8; 8],
+    created_ts: [u8; 8],
+    launch_window: [u8; 8],
+    launch_max_input_bps: [u8; 2],
+    max_referral_bps: [u8; 2],
+    curve_type: u8,
+    lp_bump: [u8; 1],
+    protocol_fee: [u8; 2],
+    swap_count: [u8; 8],
+    lp_decimals: u8,
+    flash_fee: [u8; 2],
+    flash_is_x: u8,
+    flash_required: [u8; 8],
+    price_x_cumulative: [u8; 16],
+    price_y_cumulative: [u8; 16],
+    last_update: [u8; 8],
+    native_x: u8,
+    native_y: u8,
+    max_price_impact_bps: [u8; 2],
 }
 
 #[repr(u8)]
@@ -20,8 +46,17 @@ pub enum AmmState {
     WithdrawOnly = 3u8,
 }
 
+/// Pricing curve a pool trades on. Only constant product exists today.
+#[repr(u8)]
+pub enum CurveType {
+    ConstantProduct = 0u8,
+}
+
 impl Config {
     pub const LEN: usize = size_of::<Config>();
+    /// Layout version written by `init`. Bump it whenever fields move, so
+    /// accounts in an older layout are refused rather than misread.
+    pub const VERSION: u8 = 1;
 
     // ─── Unsafe helpers ─────────────────────────────────────────────────
 
@@ -45,9 +80,9 @@ impl Config {
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
-        Ok(Self::from_bytes_unchecked(
-            account_info.borrow_data_unchecked(),
-        ))
+        let config = Self::from_bytes_unchecked(account_info.borrow_data_unchecked());
+        config.check_version()?;
+        Ok(config)
     }
 
     #[inline(always)]
@@ -55,13 +90,32 @@ impl Config {
         if account_info.data_len() != Self::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
-        Ok(Self::from_bytes_unchecked_mut(
-            account_info.borrow_mut_data_unchecked(),
-        ))
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let config = Self::from_bytes_unchecked_mut(account_info.borrow_mut_data_unchecked());
+        config.check_version()?;
+        Ok(config)
+    }
+
+    /// As `load_mut`, for a freshly created account: stamps the current
+    /// `VERSION` instead of checking it.
+    #[inline(always)]
+    pub unsafe fn init(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let config = Self::from_bytes_unchecked_mut(account_info.borrow_mut_data_unchecked());
+        config.version = Self::VERSION;
+        Ok(config)
     }
 
     // ─── Getters ────────────────────────────────────────────────────────
 
+    #[inline(always)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
     #[inline(always)]
     pub fn state(&self) -> u8 {
         self.state
@@ -90,12 +144,192 @@ impl Config {
     pub fn config_bump(&self) -> [u8; 1] {
         self.config_bump
     }
+    #[inline(always)]
+    pub fn disabled_at(&self) -> i64 {
+        i64::from_le_bytes(self.disabled_at)
+    }
+    #[inline(always)]
+    pub fn grace_seconds(&self) -> i64 {
+        i64::from_le_bytes(self.grace_seconds)
+    }
+    #[inline(always)]
+    pub fn min_lp_mint(&self) -> u64 {
+        u64::from_le_bytes(self.min_lp_mint)
+    }
+    #[inline(always)]
+    pub fn allowlist(&self) -> &Pubkey {
+        &self.allowlist
+    }
+    #[inline(always)]
+    pub fn protocol_fee_recipient(&self) -> &Pubkey {
+        &self.protocol_fee_recipient
+    }
+    #[inline(always)]
+    pub fn min_reserve_x(&self) -> u64 {
+        u64::from_le_bytes(self.min_reserve_x)
+    }
+    #[inline(always)]
+    pub fn min_reserve_y(&self) -> u64 {
+        u64::from_le_bytes(self.min_reserve_y)
+    }
+    #[inline(always)]
+    pub fn created_ts(&self) -> i64 {
+        i64::from_le_bytes(self.created_ts)
+    }
+    #[inline(always)]
+    pub fn launch_window(&self) -> i64 {
+        i64::from_le_bytes(self.launch_window)
+    }
+    #[inline(always)]
+    pub fn launch_max_input_bps(&self) -> u16 {
+        u16::from_le_bytes(self.launch_max_input_bps)
+    }
+    #[inline(always)]
+    pub fn max_referral_bps(&self) -> u16 {
+        u16::from_le_bytes(self.max_referral_bps)
+    }
+    #[inline(always)]
+    pub fn curve_type(&self) -> u8 {
+        self.curve_type
+    }
+    #[inline(always)]
+    pub fn lp_bump(&self) -> [u8; 1] {
+        self.lp_bump
+    }
+    /// Share of each swap fee, in basis points, skimmed to the protocol fee
+    /// vault instead of staying in the reserves for LPs.
+    #[inline(always)]
+    pub fn protocol_fee(&self) -> u16 {
+        u16::from_le_bytes(self.protocol_fee)
+    }
+    /// Swaps executed against the pool, for trade-size analytics.
+    #[inline(always)]
+    pub fn swap_count(&self) -> u64 {
+        u64::from_le_bytes(self.swap_count)
+    }
+    #[inline(always)]
+    pub fn lp_decimals(&self) -> u8 {
+        self.lp_decimals
+    }
+    /// Fee on flash loans, in basis points of the amount borrowed. It is
+    /// repaid into the vault, so it accrues to LPs like the swap fee.
+    #[inline(always)]
+    pub fn flash_fee(&self) -> u16 {
+        u16::from_le_bytes(self.flash_fee)
+    }
+    /// Whether the outstanding flash loan, if any, was drawn from vault X.
+    #[inline(always)]
+    pub fn flash_is_x(&self) -> bool {
+        self.flash_is_x != 0
+    }
+    /// Balance the borrowed-from vault must be back to before the
+    /// outstanding flash loan is settled, or zero when none is open.
+    #[inline(always)]
+    pub fn flash_required(&self) -> u64 {
+        u64::from_le_bytes(self.flash_required)
+    }
+    /// Running sum of the Y-per-X price, as Q64.64, times the seconds it
+    /// held. Wraps on overflow; consumers difference two snapshots with
+    /// wrapping arithmetic and divide by the time between them.
+    #[inline(always)]
+    pub fn price_x_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.price_x_cumulative)
+    }
+    /// As `price_x_cumulative`, for the X-per-Y price.
+    #[inline(always)]
+    pub fn price_y_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.price_y_cumulative)
+    }
+    /// Unix timestamp the price accumulators were last brought up to.
+    #[inline(always)]
+    pub fn last_update(&self) -> i64 {
+        i64::from_le_bytes(self.last_update)
+    }
+
+    /// Whether mint X is wrapped SOL, which deposits, swaps and withdraws
+    /// move as lamports.
+    #[inline(always)]
+    pub fn is_native_x(&self) -> bool {
+        self.native_x != 0
+    }
+    /// As `is_native_x`, for mint Y.
+    #[inline(always)]
+    pub fn is_native_y(&self) -> bool {
+        self.native_y != 0
+    }
+
+    /// Most a single swap may move the spot price, in basis points, or zero
+    /// for no cap.
+    #[inline(always)]
+    pub fn max_price_impact_bps(&self) -> u16 {
+        u16::from_le_bytes(self.max_price_impact_bps)
+    }
+
+    /// Fails on a config written in a layout other than this program's.
+    #[inline(always)]
+    pub fn check_version(&self) -> Result<(), ProgramError> {
+        if self.version != Self::VERSION {
+            return Err(crate::errors::AmmError::VersionMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Fails with a dedicated error on a config still in `Uninitialized`, so
+    /// clients can tell a non-pool account from a pool in the wrong state.
+    #[inline(always)]
+    pub fn check_initialized(&self) -> Result<(), ProgramError> {
+        if self.state == AmmState::Uninitialized as u8 {
+            return Err(crate::errors::AmmError::PoolNotInitialized.into());
+        }
+        Ok(())
+    }
+
+    /// Fails while a flash loan is open, so borrowed funds can't be cycled
+    /// through the pool and counted towards their own repayment.
+    #[inline(always)]
+    pub fn check_no_flash_loan(&self) -> Result<(), ProgramError> {
+        if self.flash_required() != 0 {
+            return Err(crate::errors::AmmError::FlashLoanActive.into());
+        }
+        Ok(())
+    }
+
+    /// Fails unless the pool's curve is one this program version can price,
+    /// so a curve from a newer version is never treated as constant product.
+    #[inline(always)]
+    pub fn check_curve(&self) -> Result<(), ProgramError> {
+        if self.curve_type != CurveType::ConstantProduct as u8 {
+            return Err(crate::errors::AmmError::UnsupportedCurve.into());
+        }
+        Ok(())
+    }
+
+    /// Whether swaps at `now` still fall in the pool's launch-protection
+    /// window, during which their input is capped.
+    #[inline(always)]
+    pub fn in_launch_window(&self, now: i64) -> bool {
+        now < self.created_ts().saturating_add(self.launch_window())
+    }
+
+    /// State as seen at `now`: a `Disabled` pool still behaves as
+    /// `WithdrawOnly` until its grace period has elapsed.
+    #[inline(always)]
+    pub fn effective_state(&self, now: i64) -> u8 {
+        if self.state == AmmState::Disabled as u8
+            && now < self.disabled_at().saturating_add(self.grace_seconds())
+        {
+            return AmmState::WithdrawOnly as u8;
+        }
+        self.state
+    }
 
     // ─── Setters ────────────────────────────────────────────────────────
 
     #[inline(always)]
     pub fn set_state(&mut self, state: u8) -> Result<(), ProgramError> {
-        if state.ge(&(AmmState::WithdrawOnly as u8)) {
+        // Uninitialized is only ever the zeroed account, never a state to
+        // move an open pool back into
+        if !(AmmState::Initialized as u8..=AmmState::WithdrawOnly as u8).contains(&state) {
             return Err(ProgramError::InvalidAccountData);
         }
         self.state = state;
@@ -129,6 +363,169 @@ impl Config {
     pub fn set_config_bump(&mut self, config_bump: [u8; 1]) {
         self.config_bump = config_bump;
     }
+    #[inline(always)]
+    pub fn set_swap_count(&mut self, swap_count: u64) {
+        self.swap_count = swap_count.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_disabled_at(&mut self, disabled_at: i64) {
+        self.disabled_at = disabled_at.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_grace_seconds(&mut self, grace_seconds: i64) -> Result<(), ProgramError> {
+        if grace_seconds.lt(&0) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.grace_seconds = grace_seconds.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_min_lp_mint(&mut self, min_lp_mint: u64) {
+        self.min_lp_mint = min_lp_mint.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_allowlist(&mut self, allowlist: Pubkey) {
+        self.allowlist = allowlist;
+    }
+    #[inline(always)]
+    pub fn set_protocol_fee_recipient(&mut self, protocol_fee_recipient: Pubkey) {
+        self.protocol_fee_recipient = protocol_fee_recipient;
+    }
+    #[inline(always)]
+    pub fn set_min_reserve_x(&mut self, min_reserve_x: u64) {
+        self.min_reserve_x = min_reserve_x.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_min_reserve_y(&mut self, min_reserve_y: u64) {
+        self.min_reserve_y = min_reserve_y.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_created_ts(&mut self, created_ts: i64) {
+        self.created_ts = created_ts.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn set_launch_protection(
+        &mut self,
+        launch_window: i64,
+        launch_max_input_bps: u16,
+    ) -> Result<(), ProgramError> {
+        if launch_window.lt(&0) || launch_max_input_bps.gt(&10_000) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.launch_window = launch_window.to_le_bytes();
+        self.launch_max_input_bps = launch_max_input_bps.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_lp_bump(&mut self, lp_bump: [u8; 1]) {
+        self.lp_bump = lp_bump;
+    }
+    #[inline(always)]
+    pub fn set_curve_type(&mut self, curve_type: u8) -> Result<(), ProgramError> {
+        if curve_type.ne(&(CurveType::ConstantProduct as u8)) {
+            return Err(crate::errors::AmmError::UnsupportedCurve.into());
+        }
+        self.curve_type = curve_type;
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_max_referral_bps(&mut self, max_referral_bps: u16) -> Result<(), ProgramError> {
+        if max_referral_bps.gt(&10_000) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.max_referral_bps = max_referral_bps.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_protocol_fee(&mut self, protocol_fee: u16) -> Result<(), ProgramError> {
+        if protocol_fee.gt(&10_000) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.protocol_fee = protocol_fee.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_lp_decimals(&mut self, lp_decimals: u8) -> Result<(), ProgramError> {
+        if lp_decimals.gt(&crate::instructions::MAX_LP_DECIMALS) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.lp_decimals = lp_decimals;
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_max_price_impact_bps(
+        &mut self,
+        max_price_impact_bps: u16,
+    ) -> Result<(), ProgramError> {
+        if max_price_impact_bps.gt(&10_000) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.max_price_impact_bps = max_price_impact_bps.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_flash_fee(&mut self, flash_fee: u16) -> Result<(), ProgramError> {
+        if flash_fee.gt(&10_000) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.flash_fee = flash_fee.to_le_bytes();
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_native(&mut self, native_x: bool, native_y: bool) {
+        self.native_x = native_x as u8;
+        self.native_y = native_y as u8;
+    }
+    /// Opens a flash loan from vault X or Y that settles once that vault is
+    /// back to `required`.
+    #[inline(always)]
+    pub fn open_flash_loan(&mut self, is_x: bool, required: u64) {
+        self.flash_is_x = is_x as u8;
+        self.flash_required = required.to_le_bytes();
+    }
+    #[inline(always)]
+    pub fn close_flash_loan(&mut self) {
+        self.flash_is_x = 0;
+        self.flash_required = [0; 8];
+    }
+    /// Brings the price accumulators up to `now`, crediting the time since
+    /// `last_update` to the price set by `reserve_x`/`reserve_y`. Call with
+    /// the reserves as they stood before the current instruction moves them,
+    /// so a trade can't weight its own price. An empty side leaves the sums
+    /// untouched and only moves the timestamp.
+    #[inline(always)]
+    pub fn update_price_cumulatives(&mut self, reserve_x: u64, reserve_y: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.last_update());
+        if elapsed <= 0 {
+            return;
+        }
+        if reserve_x != 0 && reserve_y != 0 {
+            let price_x = ((reserve_y as u128) << 64) / reserve_x as u128;
+            let price_y = ((reserve_x as u128) << 64) / reserve_y as u128;
+            self.price_x_cumulative = self
+                .price_x_cumulative()
+                .wrapping_add(price_x.wrapping_mul(elapsed as u128))
+                .to_le_bytes();
+            self.price_y_cumulative = self
+                .price_y_cumulative()
+                .wrapping_add(price_y.wrapping_mul(elapsed as u128))
+                .to_le_bytes();
+        }
+        self.last_update = now.to_le_bytes();
+    }
+    /// Counts one more swap, saturating rather than wrapping.
+    #[inline(always)]
+    pub fn increment_swap_count(&mut self) {
+        self.swap_count = self.swap_count().saturating_add(1).to_le_bytes();
+    }
+
+    /// Disables the pool at `now`, keeping withdraws open for `grace_seconds`.
+    #[inline(always)]
+    pub fn disable(&mut self, now: i64, grace_seconds: i64) -> Result<(), ProgramError> {
+        self.set_grace_seconds(grace_seconds)?;
+        self.set_disabled_at(now);
+        self.set_state(AmmState::Disabled as u8)
+    }
 
     #[inline(always)]
     pub fn set_inner(
@@ -160,4 +557,133 @@ impl Config {
             None
         }
     }
+
+    /// The output-mint allowlist swaps must consult, if one was configured.
+    #[inline(always)]
+    pub fn has_allowlist(&self) -> Option<&Pubkey> {
+        if self.allowlist.iter().any(|&b| b != 0) {
+            Some(&self.allowlist)
+        } else {
+            None
+        }
+    }
+}
+
+// ─── Allowlist ──────────────────────────────────────────────────────────────
+
+/// Program-owned account holding a flat list of mints a pool may deliver as
+/// swap output.
+pub struct Allowlist;
+
+impl Allowlist {
+    #[inline(always)]
+    pub fn check(
+        account: &AccountInfo,
+        expected: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if account.key() != expected {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let data = account.try_borrow_data()?;
+        if !data
+            .chunks_exact(size_of::<Pubkey>())
+            .any(|entry| entry == mint)
+        {
+            return Err(crate::errors::AmmError::OutputNotAllowed.into());
+        }
+        Ok(())
+    }
+}
+
+// ─── Position ───────────────────────────────────────────────────────────────
+
+/// Per-user record of the LP a wallet holds in a pool, at
+/// `[b"position", config, owner]`.
+#[repr(C)]
+pub struct Position {
+    owner: Pubkey,
+    config: Pubkey,
+    lp_amount: [u8; 8],
+    bump: [u8; 1],
+}
+
+impl Position {
+    pub const LEN: usize = size_of::<Position>();
+
+    // ─── Load helpers ───────────────────────────────────────────────────
+
+    #[inline(always)]
+    pub unsafe fn load(account_info: &AccountInfo) -> Result<&Self, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(&*(account_info.borrow_data_unchecked().as_ptr() as *const Position))
+    }
+
+    #[inline(always)]
+    pub unsafe fn load_mut(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        if account_info.data_len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(&mut *(account_info.borrow_mut_data_unchecked().as_mut_ptr() as *mut Position))
+    }
+
+    // ─── Getters ────────────────────────────────────────────────────────
+
+    #[inline(always)]
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+    #[inline(always)]
+    pub fn config(&self) -> &Pubkey {
+        &self.config
+    }
+    #[inline(always)]
+    pub fn lp_amount(&self) -> u64 {
+        u64::from_le_bytes(self.lp_amount)
+    }
+    #[inline(always)]
+    pub fn bump(&self) -> [u8; 1] {
+        self.bump
+    }
+
+    // ─── Setters ────────────────────────────────────────────────────────
+
+    #[inline(always)]
+    pub fn set_lp_amount(&mut self, lp_amount: u64) {
+        self.lp_amount = lp_amount.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, owner: Pubkey, config: Pubkey, bump: [u8; 1]) {
+        self.owner = owner;
+        self.config = config;
+        self.set_lp_amount(0);
+        self.bump = bump;
+    }
+
+    /// Loads the position `owner` holds in `config`, for deposit/withdraw to
+    /// keep in sync with the LP they mint or burn.
+    #[inline(always)]
+    pub unsafe fn load_for<'a>(
+        account_info: &'a AccountInfo,
+        owner: &Pubkey,
+        config: &Pubkey,
+    ) -> Result<&'a mut Self, ProgramError> {
+        let position = Self::load_mut(account_info)?;
+        if position.owner() != owner || position.config() != config {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(position)
+    }
 }
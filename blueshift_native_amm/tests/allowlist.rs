@@ -0,0 +1,142 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 1_000;
+
+fn allowlist_address(pool: &Pool) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"allowlist", pool.config.as_ref()], &PROGRAM_ID)
+}
+
+fn init_allowlist(pool: &Pool, authority: &Pubkey, mints: &[Pubkey]) -> Instruction {
+    let (allowlist, bump) = allowlist_address(pool);
+    let mut data = vec![9, 1, bump];
+    for mint in mints {
+        data.extend_from_slice(mint.as_ref());
+    }
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(allowlist, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ],
+    )
+}
+
+/// A pool administered by the returned authority whose allowlist holds
+/// only `allowed`, and a trader holding X.
+fn restricted(
+    allowed: impl FnOnce(&Pool) -> Pubkey,
+) -> (Pool, Pubkey, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let (allowlist, _) = allowlist_address(&pool);
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+        config.set_allowlist(allowlist.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+    let data = allowed(&pool).to_bytes().to_vec();
+    accounts.push((
+        allowlist,
+        Account {
+            lamports: rent_exempt(data.len()),
+            data,
+            owner: PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    ));
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+    (pool, authority, trader, accounts)
+}
+
+fn sell_x(pool: &Pool, trader: &User) -> Instruction {
+    let (allowlist, _) = allowlist_address(pool);
+    pool.swap(
+        trader,
+        SwapData::exact_in(true, AMOUNT_IN, 1),
+        &[AccountMeta::new_readonly(allowlist, false)],
+    )
+}
+
+#[test]
+fn init_allowlist_points_the_config_at_the_new_list() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let (allowlist, _) = allowlist_address(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+    accounts.push((allowlist, system_account(0)));
+    accounts.push(keyed_account_for_system_program());
+
+    let result = mollusk.process_and_validate_instruction(
+        &init_allowlist(&pool, &authority, &[pool.mint_y]),
+        &accounts,
+        &[Check::success()],
+    );
+    let list = result.get_account(&allowlist).unwrap();
+    assert_eq!(list.owner, PROGRAM_ID);
+    assert_eq!(list.data, pool.mint_y.to_bytes());
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).has_allowlist(),
+        Some(&allowlist.to_bytes())
+    );
+}
+
+#[test]
+fn init_allowlist_rejects_a_second_list() {
+    let mollusk = mollusk();
+    let (pool, authority, _, mut accounts) = restricted(|pool| pool.mint_y);
+    accounts.push(keyed_account_for_system_program());
+
+    mollusk.process_and_validate_instruction(
+        &init_allowlist(&pool, &authority, &[pool.mint_x]),
+        &accounts,
+        &[Check::err(ProgramError::AccountAlreadyInitialized)],
+    );
+}
+
+#[test]
+fn swap_delivers_an_allowlisted_output_mint() {
+    let mollusk = mollusk();
+    let (pool, _, trader, accounts) = restricted(|pool| pool.mint_y);
+
+    let result = mollusk.process_and_validate_instruction(
+        &sell_x(&pool, &trader),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &trader.x_ata), 0);
+    assert!(amount_at(&result, &trader.y_ata) > 0);
+}
+
+#[test]
+fn swap_refuses_an_output_mint_off_the_allowlist() {
+    let mollusk = mollusk();
+    let (pool, _, trader, accounts) = restricted(|pool| pool.mint_x);
+
+    mollusk.process_and_validate_instruction(
+        &sell_x(&pool, &trader),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::OutputNotAllowed as u32,
+        ))],
+    );
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use blueshift_native_amm::{AmmState, MINIMUM_LIQUIDITY};
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Reserve dust left behind the locked minimum once every LP has exited.
+const DUST: u64 = 37;
+
+struct Admin {
+    authority: Pubkey,
+    locked_lp_ata: Pubkey,
+    destination_x: Pubkey,
+    destination_y: Pubkey,
+}
+
+fn close_config(pool: &Pool, admin: &Admin) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[26],
+        vec![
+            AccountMeta::new(admin.authority, true),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(admin.authority, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(admin.locked_lp_ata, false),
+            AccountMeta::new(admin.destination_x, false),
+            AccountMeta::new(admin.destination_y, false),
+        ],
+    )
+}
+
+/// A retired pool in `state` where only the locked minimum is left, with
+/// `DUST` of each side still in the vaults.
+fn setup(state: AmmState) -> (Pool, Admin, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let admin = Admin {
+        authority,
+        locked_lp_ata: associated_token_address(&pool.config, &pool.mint_lp),
+        destination_x: associated_token_address(&authority, &pool.mint_x),
+        destination_y: associated_token_address(&authority, &pool.mint_y),
+    };
+
+    let mut accounts = pool.accounts(MINIMUM_LIQUIDITY, |config| {
+        config.set_authority(authority.to_bytes());
+        config.set_state(state as u8).unwrap();
+    });
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, DUST),
+    );
+    set_account(
+        &mut accounts,
+        &pool.vault_y,
+        token_account(&pool.mint_y, &pool.config, DUST),
+    );
+    accounts.push((authority, system_account(1_000_000_000)));
+    accounts.push((
+        admin.locked_lp_ata,
+        token_account(&pool.mint_lp, &pool.config, MINIMUM_LIQUIDITY),
+    ));
+    accounts.push((
+        admin.destination_x,
+        token_account(&pool.mint_x, &authority, 0),
+    ));
+    accounts.push((
+        admin.destination_y,
+        token_account(&pool.mint_y, &authority, 0),
+    ));
+    (pool, admin, accounts)
+}
+
+fn assert_closed(pool: &Pool, admin: &Admin, result: &mollusk_svm::result::InstructionResult) {
+    let config = result.get_account(&pool.config).unwrap();
+    assert_eq!(config.lamports, 0);
+    assert_eq!(config.owner, solana_sdk::system_program::ID);
+
+    // The locked LP is burned and the dust it backed swept out
+    assert_eq!(
+        token_amount(result.get_account(&admin.locked_lp_ata).unwrap()),
+        0
+    );
+    assert_eq!(token_amount(result.get_account(&pool.vault_x).unwrap()), 0);
+    assert_eq!(
+        token_amount(result.get_account(&admin.destination_x).unwrap()),
+        DUST
+    );
+    assert_eq!(
+        token_amount(result.get_account(&admin.destination_y).unwrap()),
+        DUST
+    );
+}
+
+#[test]
+fn close_config_burns_the_locked_lp_and_sweeps_the_dust() {
+    let mollusk = mollusk();
+    let (pool, admin, accounts) = setup(AmmState::WithdrawOnly);
+
+    let result = mollusk.process_and_validate_instruction(
+        &close_config(&pool, &admin),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_closed(&pool, &admin, &result);
+}
+
+#[test]
+fn close_config_accepts_a_disabled_pool() {
+    let mollusk = mollusk();
+    let (pool, admin, accounts) = setup(AmmState::Disabled);
+
+    let result = mollusk.process_and_validate_instruction(
+        &close_config(&pool, &admin),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_closed(&pool, &admin, &result);
+}
+
+#[test]
+fn close_config_rejects_lp_outstanding_beyond_the_locked_minimum() {
+    let mollusk = mollusk();
+    let (pool, admin, mut accounts) = setup(AmmState::WithdrawOnly);
+    set_account(
+        &mut accounts,
+        &pool.mint_lp,
+        lp_mint(MINIMUM_LIQUIDITY + 1, &pool.config),
+    );
+
+    mollusk.process_and_validate_instruction(
+        &close_config(&pool, &admin),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn close_config_rejects_a_live_pool() {
+    let mollusk = mollusk();
+    let (pool, admin, accounts) = setup(AmmState::Initialized);
+
+    mollusk.process_and_validate_instruction(
+        &close_config(&pool, &admin),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
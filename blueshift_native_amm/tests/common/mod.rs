@@ -0,0 +1,472 @@
+//! Pool fixtures shared by the AMM tests. The program must be built with
+//! `cargo build-sbf` first, so `target/deploy` holds its `.so`.
+
+#![allow(dead_code)]
+
+use blueshift_native_amm::{AmmState, Config};
+use std::path::Path;
+
+use mollusk_svm::{result::InstructionResult, Mollusk};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    rent::Rent,
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(blueshift_native_amm::ID);
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ATA_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+pub const MINT_LEN: usize = 82;
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Token-2022 extension types, written as `type u16, length u16` TLV
+/// entries after the account type byte at offset 165.
+const IMMUTABLE_OWNER: u16 = 7;
+const NON_TRANSFERABLE: u16 = 9;
+const NON_TRANSFERABLE_ACCOUNT: u16 = 13;
+
+/// The program as built by `cargo build-sbf`, relative to the crate root.
+const PROGRAM_SO: &str = "target/deploy/blueshift_native_amm";
+
+pub fn mollusk() -> Mollusk {
+    assert!(
+        Path::new(PROGRAM_SO).with_extension("so").exists(),
+        "{PROGRAM_SO}.so is missing, run `cargo build-sbf` before `cargo test`"
+    );
+    let mut mollusk = Mollusk::new(&PROGRAM_ID, PROGRAM_SO);
+    mollusk_svm_programs_token::token::add_program(&mut mollusk);
+    mollusk_svm_programs_token::token2022::add_program(&mut mollusk);
+    mollusk_svm_programs_token::associated_token::add_program(&mut mollusk);
+    mollusk
+}
+
+/// The program with the clock set to `unix_timestamp`.
+pub fn mollusk_at(unix_timestamp: i64) -> Mollusk {
+    let mut mollusk = mollusk();
+    mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+    mollusk
+}
+
+pub fn rent_exempt(len: usize) -> u64 {
+    Rent::default().minimum_balance(len)
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::ID)
+}
+
+/// An initialized SPL Token mint with `supply` and no authorities.
+pub fn mint(supply: u64, decimals: u8) -> Account {
+    let mut data = vec![0u8; MINT_LEN];
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1;
+    Account {
+        lamports: rent_exempt(MINT_LEN),
+        data,
+        owner: TOKEN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// An initialized SPL Token account holding `amount` of `mint` for `owner`.
+pub fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+    data[0..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = 1;
+    Account {
+        lamports: rent_exempt(TOKEN_ACCOUNT_LEN),
+        data,
+        owner: TOKEN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// A wrapped SOL account holding `amount` for `owner`, backed by as many
+/// lamports over its rent-exempt reserve.
+pub fn native_token_account(owner: &Pubkey, amount: u64) -> Account {
+    let reserve = rent_exempt(TOKEN_ACCOUNT_LEN);
+    let mut account = token_account(&NATIVE_MINT, owner, amount);
+    account.data[109..113].copy_from_slice(&1u32.to_le_bytes());
+    account.data[113..121].copy_from_slice(&reserve.to_le_bytes());
+    account.lamports = reserve + amount;
+    account
+}
+
+/// Appends the Token-2022 account type and zero-length `extensions` to a
+/// legacy base layout padded out to the account type offset.
+fn with_extensions(mut data: Vec<u8>, account_type: u8, extensions: &[u16]) -> Account {
+    data.resize(TOKEN_ACCOUNT_LEN, 0);
+    data.push(account_type);
+    for extension in extensions {
+        data.extend_from_slice(&extension.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+    }
+    Account {
+        lamports: rent_exempt(data.len()),
+        data,
+        owner: TOKEN_2022_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// A pool's LP mint with `supply`, minted by `authority`.
+pub fn lp_mint(supply: u64, authority: &Pubkey) -> Account {
+    let mut account = mint(supply, 6);
+    account.data[0] = 1;
+    account.data[4..36].copy_from_slice(authority.as_ref());
+    account
+}
+
+/// A soulbound pool's LP mint: Token-2022 with the NonTransferable
+/// extension, minted by `authority`.
+pub fn soulbound_mint(supply: u64, authority: &Pubkey) -> Account {
+    with_extensions(lp_mint(supply, authority).data, 1, &[NON_TRANSFERABLE])
+}
+
+/// A Token-2022 account of a soulbound LP mint, with the extensions
+/// Token-2022 gives every account of a NonTransferable mint.
+pub fn soulbound_token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    with_extensions(
+        token_account(mint, owner, amount).data,
+        2,
+        &[IMMUTABLE_OWNER, NON_TRANSFERABLE_ACCOUNT],
+    )
+}
+
+/// Replaces the account at `key` in a fixture list.
+pub fn set_account(accounts: &mut [(Pubkey, Account)], key: &Pubkey, account: Account) {
+    accounts.iter_mut().find(|(k, _)| k == key).unwrap().1 = account;
+}
+
+pub fn token_amount(account: &Account) -> u64 {
+    u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Reads the `Config` held by a resulting account.
+pub fn config_of(account: &Account) -> &Config {
+    assert_eq!(account.data.len(), Config::LEN);
+    unsafe { Config::from_bytes_unchecked(&account.data) }
+}
+
+/// An initialized constant-product pool with both vaults funded.
+pub struct Pool {
+    pub config: Pubkey,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub mint_lp: Pubkey,
+    pub vault_x: Pubkey,
+    pub vault_y: Pubkey,
+}
+
+impl Pool {
+    pub const SEED: u64 = 1;
+    pub const FEE_BPS: u16 = 30;
+
+    pub fn new() -> Self {
+        Self::with_mints(Pubkey::new_unique(), Pubkey::new_unique())
+    }
+
+    pub fn with_mints(mint_x: Pubkey, mint_y: Pubkey) -> Self {
+        let (config, _) = Pubkey::find_program_address(
+            &[
+                b"config",
+                &Self::SEED.to_le_bytes(),
+                mint_x.as_ref(),
+                mint_y.as_ref(),
+            ],
+            &PROGRAM_ID,
+        );
+        let (mint_lp, _) =
+            Pubkey::find_program_address(&[b"mint_lp", config.as_ref()], &PROGRAM_ID);
+        Self {
+            config,
+            mint_x,
+            mint_y,
+            mint_lp,
+            vault_x: associated_token_address(&config, &mint_x),
+            vault_y: associated_token_address(&config, &mint_y),
+        }
+    }
+
+    /// The config and LP mint bumps, as Initialize takes them.
+    pub fn bumps(&self) -> (u8, u8) {
+        let (_, config_bump) = Pubkey::find_program_address(
+            &[
+                b"config",
+                &Self::SEED.to_le_bytes(),
+                self.mint_x.as_ref(),
+                self.mint_y.as_ref(),
+            ],
+            &PROGRAM_ID,
+        );
+        let (_, lp_bump) =
+            Pubkey::find_program_address(&[b"mint_lp", self.config.as_ref()], &PROGRAM_ID);
+        (config_bump, lp_bump)
+    }
+
+    /// Latest-version Initialize data for this pool, after the version
+    /// byte: no authority, floors, launch protection or referrals.
+    pub fn initialize_data(&self) -> Vec<u8> {
+        let (config_bump, lp_bump) = self.bumps();
+        let mut data = Self::SEED.to_le_bytes().to_vec();
+        data.extend_from_slice(&Self::FEE_BPS.to_le_bytes());
+        data.extend_from_slice(self.mint_x.as_ref());
+        data.extend_from_slice(self.mint_y.as_ref());
+        data.push(config_bump);
+        data.push(lp_bump);
+        data.extend_from_slice(&0u64.to_le_bytes()); // min_lp_mint
+        data.extend_from_slice(&[0; 32]); // protocol_fee_recipient
+        data.extend_from_slice(&0u64.to_le_bytes()); // min_reserve_x
+        data.extend_from_slice(&0u64.to_le_bytes()); // min_reserve_y
+        data.push(0); // soulbound_lp
+        data.extend_from_slice(&0i64.to_le_bytes()); // launch_window
+        data.extend_from_slice(&0u16.to_le_bytes()); // launch_max_input_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // max_referral_bps
+        data.push(0); // curve_type
+        data.extend_from_slice(&[0; 32]); // authority
+        data.push(6); // lp_decimals
+        data.push(9); // max_decimals_spread
+        data
+    }
+
+    /// The pool's accounts, with `reserve` in each vault and as LP supply.
+    /// `configure` adjusts the config before it is written out.
+    pub fn accounts(
+        &self,
+        reserve: u64,
+        configure: impl FnOnce(&mut Config),
+    ) -> Vec<(Pubkey, Account)> {
+        let (config_bump, lp_bump) = self.bumps();
+
+        let mut data = vec![0u8; Config::LEN];
+        data[0] = Config::VERSION;
+        let config = unsafe { Config::from_bytes_unchecked_mut(&mut data) };
+        config.set_state(AmmState::Initialized as u8).unwrap();
+        config.set_seed(Self::SEED);
+        config.set_mint_x(self.mint_x.to_bytes());
+        config.set_mint_y(self.mint_y.to_bytes());
+        config.set_fee(Self::FEE_BPS).unwrap();
+        config.set_config_bump([config_bump]);
+        config.set_lp_bump([lp_bump]);
+        configure(config);
+
+        vec![
+            (
+                self.config,
+                Account {
+                    lamports: rent_exempt(Config::LEN),
+                    data,
+                    owner: PROGRAM_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            ),
+            (self.mint_x, mint(u64::MAX, 6)),
+            (self.mint_y, mint(u64::MAX, 6)),
+            (self.mint_lp, lp_mint(reserve, &self.config)),
+            (
+                self.vault_x,
+                token_account(&self.mint_x, &self.config, reserve),
+            ),
+            (
+                self.vault_y,
+                token_account(&self.mint_y, &self.config, reserve),
+            ),
+            mollusk_svm_programs_token::token::keyed_account(),
+        ]
+    }
+}
+
+/// A wallet with an associated token account for each of a pool's mints.
+pub struct User {
+    pub key: Pubkey,
+    pub x_ata: Pubkey,
+    pub y_ata: Pubkey,
+    pub lp_ata: Pubkey,
+}
+
+impl User {
+    pub fn new(pool: &Pool) -> Self {
+        let key = Pubkey::new_unique();
+        Self {
+            key,
+            x_ata: associated_token_address(&key, &pool.mint_x),
+            y_ata: associated_token_address(&key, &pool.mint_y),
+            lp_ata: associated_token_address(&key, &pool.mint_lp),
+        }
+    }
+
+    /// The funded wallet and its token accounts, holding `x`, `y` and `lp`.
+    pub fn accounts(&self, pool: &Pool, x: u64, y: u64, lp: u64) -> Vec<(Pubkey, Account)> {
+        vec![
+            (self.key, system_account(1_000_000_000)),
+            (self.x_ata, token_account(&pool.mint_x, &self.key, x)),
+            (self.y_ata, token_account(&pool.mint_y, &self.key, y)),
+            (self.lp_ata, token_account(&pool.mint_lp, &self.key, lp)),
+        ]
+    }
+}
+
+/// Latest-version Deposit data.
+#[derive(Clone, Copy, Default)]
+pub struct DepositData {
+    pub amount: u64,
+    pub max_x: u64,
+    pub max_y: u64,
+    pub expiration: i64,
+    pub min_lp_out: u64,
+}
+
+/// Latest-version Withdraw data.
+#[derive(Clone, Copy, Default)]
+pub struct WithdrawData {
+    pub amount: u64,
+    pub min_x: u64,
+    pub min_y: u64,
+    pub expiration: i64,
+    pub allow_zero_slippage: bool,
+}
+
+/// Latest-version Swap data, with no oracle, referral or slot deadline.
+#[derive(Clone, Copy)]
+pub struct SwapData {
+    pub is_x: bool,
+    pub amount: u64,
+    pub min: u64,
+    pub expiration: i64,
+    pub is_exact_in: bool,
+}
+
+impl SwapData {
+    /// Sells exactly `amount` of X, or of Y, for at least `min`.
+    pub fn exact_in(is_x: bool, amount: u64, min: u64) -> Self {
+        Self {
+            is_x,
+            amount,
+            min,
+            expiration: 0,
+            is_exact_in: true,
+        }
+    }
+
+    /// Buys exactly `amount` of the other side for at most `max_in`.
+    pub fn exact_out(is_x: bool, amount: u64, max_in: u64) -> Self {
+        Self {
+            is_exact_in: false,
+            ..Self::exact_in(is_x, amount, max_in)
+        }
+    }
+}
+
+impl Pool {
+    /// A Deposit by `user`, with `trailing` after the fixed accounts.
+    pub fn deposit(&self, user: &User, data: DepositData, trailing: &[AccountMeta]) -> Instruction {
+        let mut bytes = vec![1, 2];
+        bytes.extend_from_slice(&data.amount.to_le_bytes());
+        bytes.extend_from_slice(&data.max_x.to_le_bytes());
+        bytes.extend_from_slice(&data.max_y.to_le_bytes());
+        bytes.extend_from_slice(&data.expiration.to_le_bytes());
+        bytes.extend_from_slice(&data.min_lp_out.to_le_bytes());
+
+        let mut metas = vec![
+            AccountMeta::new(user.key, true),
+            AccountMeta::new(self.mint_lp, false),
+            AccountMeta::new(self.vault_x, false),
+            AccountMeta::new(self.vault_y, false),
+            AccountMeta::new(user.x_ata, false),
+            AccountMeta::new(user.y_ata, false),
+            AccountMeta::new(user.lp_ata, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ];
+        metas.extend_from_slice(trailing);
+        Instruction::new_with_bytes(PROGRAM_ID, &bytes, metas)
+    }
+
+    /// A Withdraw by `user`.
+    pub fn withdraw(&self, user: &User, data: WithdrawData) -> Instruction {
+        let mut bytes = vec![2, 2];
+        bytes.extend_from_slice(&data.amount.to_le_bytes());
+        bytes.extend_from_slice(&data.min_x.to_le_bytes());
+        bytes.extend_from_slice(&data.min_y.to_le_bytes());
+        bytes.extend_from_slice(&data.expiration.to_le_bytes());
+        bytes.push(data.allow_zero_slippage as u8);
+
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &bytes,
+            vec![
+                AccountMeta::new(user.key, true),
+                AccountMeta::new(self.mint_lp, false),
+                AccountMeta::new(self.vault_x, false),
+                AccountMeta::new(self.vault_y, false),
+                AccountMeta::new(user.x_ata, false),
+                AccountMeta::new(user.y_ata, false),
+                AccountMeta::new(user.lp_ata, false),
+                AccountMeta::new(self.config, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+        )
+    }
+
+    /// A Swap by `user`, with `trailing` after the fixed accounts.
+    pub fn swap(&self, user: &User, data: SwapData, trailing: &[AccountMeta]) -> Instruction {
+        let mut bytes = vec![3, 5, data.is_x as u8];
+        bytes.extend_from_slice(&data.amount.to_le_bytes());
+        bytes.extend_from_slice(&data.min.to_le_bytes());
+        bytes.extend_from_slice(&data.expiration.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // oracle_price
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // max_divergence_bps
+        bytes.extend_from_slice(&0u16.to_le_bytes()); // referral_bps
+        bytes.push(data.is_exact_in as u8);
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // expiration_slot
+
+        let mut metas = vec![
+            AccountMeta::new(user.key, true),
+            AccountMeta::new_readonly(self.mint_lp, false),
+            AccountMeta::new(user.x_ata, false),
+            AccountMeta::new(user.y_ata, false),
+            AccountMeta::new(self.vault_x, false),
+            AccountMeta::new(self.vault_y, false),
+            AccountMeta::new(self.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+        metas.extend_from_slice(trailing);
+        Instruction::new_with_bytes(PROGRAM_ID, &bytes, metas)
+    }
+}
+
+/// Carries an instruction's resulting accounts over into `accounts`, for the
+/// next instruction in a sequence.
+pub fn apply(accounts: &mut [(Pubkey, Account)], result: &InstructionResult) {
+    for (key, account) in &result.resulting_accounts {
+        if let Some(entry) = accounts.iter_mut().find(|(k, _)| k == key) {
+            entry.1 = account.clone();
+        }
+    }
+}
+
+/// Balance of the token account at `key` after `result`.
+pub fn amount_at(result: &InstructionResult, key: &Pubkey) -> u64 {
+    token_amount(result.get_account(key).unwrap())
+}
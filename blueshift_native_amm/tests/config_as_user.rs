@@ -0,0 +1,70 @@
+mod common;
+
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT: u64 = 100_000;
+
+/// A pool and a user holding `AMOUNT` of X, Y and LP.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(user.accounts(&pool, AMOUNT, AMOUNT, AMOUNT));
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+    (pool, user, accounts)
+}
+
+/// A swap, a deposit and a withdraw by `user`.
+fn instructions(pool: &Pool, user: &User) -> [Instruction; 3] {
+    [
+        pool.swap(user, SwapData::exact_in(true, AMOUNT, 1), &[]),
+        pool.deposit(
+            user,
+            DepositData {
+                amount: AMOUNT,
+                max_x: AMOUNT,
+                max_y: AMOUNT,
+                ..Default::default()
+            },
+            &[],
+        ),
+        pool.withdraw(
+            user,
+            WithdrawData {
+                amount: AMOUNT,
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+#[test]
+fn swap_deposit_and_withdraw_accept_a_user_wallet() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    for instruction in instructions(&pool, &user) {
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+    }
+}
+
+#[test]
+fn swap_deposit_and_withdraw_reject_the_config_as_user() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    for mut instruction in instructions(&pool, &user) {
+        instruction.accounts[0].pubkey = pool.config;
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::InvalidArgument)],
+        );
+    }
+}
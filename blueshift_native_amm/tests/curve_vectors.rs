@@ -0,0 +1,298 @@
+//! Hand-computed constant-product vectors, asserted against what Deposit,
+//! Withdraw and Swap actually move. Every vector divides exactly, so a
+//! change to the curve's rounding or precision handling shows up as a
+//! mismatch rather than an off-by-one that could go either way.
+
+mod common;
+
+use blueshift_native_amm::MINIMUM_LIQUIDITY;
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// What the user starts with on each side, enough for every vector.
+const BALANCE: u64 = u64::MAX / 4;
+
+struct User {
+    key: Pubkey,
+    x_ata: Pubkey,
+    y_ata: Pubkey,
+    lp_ata: Pubkey,
+}
+
+/// A pool holding `x` and `y` against `supply` LP, and a user with
+/// `BALANCE` of X and Y and `user_lp` of the supply.
+fn setup(x: u64, y: u64, supply: u64, user_lp: u64) -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let key = Pubkey::new_unique();
+    let user = User {
+        key,
+        x_ata: associated_token_address(&key, &pool.mint_x),
+        y_ata: associated_token_address(&key, &pool.mint_y),
+        lp_ata: associated_token_address(&key, &pool.mint_lp),
+    };
+
+    let mut accounts = pool.accounts(0, |_| {});
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, x),
+    );
+    set_account(
+        &mut accounts,
+        &pool.vault_y,
+        token_account(&pool.mint_y, &pool.config, y),
+    );
+    set_account(&mut accounts, &pool.mint_lp, lp_mint(supply, &pool.config));
+    accounts.push((key, system_account(1_000_000_000)));
+    accounts.push((user.x_ata, token_account(&pool.mint_x, &key, BALANCE)));
+    accounts.push((user.y_ata, token_account(&pool.mint_y, &key, BALANCE)));
+    accounts.push((user.lp_ata, token_account(&pool.mint_lp, &key, user_lp)));
+    (pool, user, accounts)
+}
+
+fn amount(result: &mollusk_svm::result::InstructionResult, key: &Pubkey) -> u64 {
+    token_amount(result.get_account(key).unwrap())
+}
+
+fn run(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+) -> mollusk_svm::result::InstructionResult {
+    mollusk.process_and_validate_instruction(instruction, accounts, &[Check::success()])
+}
+
+// ─── Deposit ────────────────────────────────────────────────────────────────
+
+/// `(x, y, supply, lp) -> (x_in, y_in)`: each side is `reserve * lp /
+/// supply`, rounded up in the pool's favour.
+const DEPOSIT_VECTORS: &[(u64, u64, u64, u64, u64, u64)] = &[
+    (1_000_000, 4_000_000, 2_000_000, 500_000, 250_000, 1_000_000),
+    (1_000_001, 3, 1_000_000, 1, 2, 1),
+    (
+        4_000_000_000_000_000_000,
+        1_000_000,
+        2_000_000,
+        1_000_000,
+        2_000_000_000_000_000_000,
+        500_000,
+    ),
+];
+
+fn deposit(pool: &Pool, user: &User, lp: u64, trailing: &[AccountMeta]) -> Instruction {
+    let mut data = vec![1, 2];
+    data.extend_from_slice(&lp.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(user.key, true),
+        AccountMeta::new(pool.mint_lp, false),
+        AccountMeta::new(pool.vault_x, false),
+        AccountMeta::new(pool.vault_y, false),
+        AccountMeta::new(user.x_ata, false),
+        AccountMeta::new(user.y_ata, false),
+        AccountMeta::new(user.lp_ata, false),
+        AccountMeta::new(pool.config, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+    ];
+    metas.extend_from_slice(trailing);
+    Instruction::new_with_bytes(PROGRAM_ID, &data, metas)
+}
+
+#[test]
+fn deposit_vectors() {
+    let mollusk = mollusk();
+    for &(x, y, supply, lp, x_in, y_in) in DEPOSIT_VECTORS {
+        let (pool, user, mut accounts) = setup(x, y, supply, 0);
+        accounts.push(keyed_account_for_system_program());
+        accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+
+        let result = run(&mollusk, &deposit(&pool, &user, lp, &[]), &accounts);
+
+        assert_eq!(amount(&result, &pool.vault_x), x + x_in, "x for {lp} LP");
+        assert_eq!(amount(&result, &pool.vault_y), y + y_in, "y for {lp} LP");
+        assert_eq!(amount(&result, &user.lp_ata), lp);
+    }
+}
+
+#[test]
+fn first_deposit_vector() {
+    let mollusk = mollusk();
+    let (pool, user, mut accounts) = setup(0, 0, 0, 0);
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    accounts.push((locked_lp_ata, system_account(0)));
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+
+    // The first depositor pays their maximums, setting the price, and the
+    // locked minimum comes out of the LP they asked for
+    let lp = 1_000_000;
+    let result = run(
+        &mollusk,
+        &deposit(&pool, &user, lp, &[AccountMeta::new(locked_lp_ata, false)]),
+        &accounts,
+    );
+
+    assert_eq!(amount(&result, &pool.vault_x), BALANCE);
+    assert_eq!(amount(&result, &pool.vault_y), BALANCE);
+    assert_eq!(amount(&result, &user.lp_ata), lp - MINIMUM_LIQUIDITY);
+    assert_eq!(amount(&result, &locked_lp_ata), MINIMUM_LIQUIDITY);
+}
+
+// ─── Withdraw ───────────────────────────────────────────────────────────────
+
+/// `(x, y, supply, lp) -> (x_out, y_out)`: each side is `reserve * lp /
+/// supply`, rounded down in the pool's favour.
+const WITHDRAW_VECTORS: &[(u64, u64, u64, u64, u64, u64)] = &[
+    // Full withdraw: the whole supply takes the whole pool
+    (
+        1_000_000, 4_000_000, 2_000_000, 2_000_000, 1_000_000, 4_000_000,
+    ),
+    (
+        1_000_000, 4_000_000, 2_000_000, 1_000_000, 500_000, 2_000_000,
+    ),
+    (
+        4_000_000_000_000_000_000,
+        1_000_000,
+        2_000_000,
+        1_000_000,
+        2_000_000_000_000_000_000,
+        500_000,
+    ),
+];
+
+/// Burns `lp` with the expected payout as the minimums, so a short payout
+/// fails the slippage check as well as the assertions.
+fn withdraw(pool: &Pool, user: &User, lp: u64, min_x: u64, min_y: u64) -> Instruction {
+    let mut data = vec![2, 2];
+    data.extend_from_slice(&lp.to_le_bytes());
+    data.extend_from_slice(&min_x.to_le_bytes());
+    data.extend_from_slice(&min_y.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.push(0);
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(user.key, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(user.x_ata, false),
+            AccountMeta::new(user.y_ata, false),
+            AccountMeta::new(user.lp_ata, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn withdraw_vectors() {
+    let mollusk = mollusk();
+    for &(x, y, supply, lp, x_out, y_out) in WITHDRAW_VECTORS {
+        let (pool, user, accounts) = setup(x, y, supply, lp);
+
+        let result = run(
+            &mollusk,
+            &withdraw(&pool, &user, lp, x_out, y_out),
+            &accounts,
+        );
+
+        assert_eq!(amount(&result, &pool.vault_x), x - x_out, "x for {lp} LP");
+        assert_eq!(amount(&result, &pool.vault_y), y - y_out, "y for {lp} LP");
+        assert_eq!(amount(&result, &user.x_ata), BALANCE + x_out);
+        assert_eq!(amount(&result, &user.y_ata), BALANCE + y_out);
+        assert_eq!(amount(&result, &user.lp_ata), 0);
+    }
+}
+
+// ─── Swap ───────────────────────────────────────────────────────────────────
+
+/// `(is_x, x, y, amount_in) -> amount_out` at the pool's 30 bps fee. The
+/// output is priced on `amount_in` less the fee, which every vector keeps
+/// whole, and leaves the output reserve at exactly `k / (reserve_in +
+/// net_in)`.
+const SWAP_VECTORS: &[(bool, u64, u64, u64, u64)] = &[
+    // Near-empty: 997 net X doubles the X side, halving 2 Y to 1
+    (true, 997, 2, 1_000, 1),
+    // 2_991_000 net X quadruples the X side
+    (true, 997_000, 1_000_000, 3_000_000, 750_000),
+    // Y for X: 997_000 net Y doubles the Y side
+    (false, 2_000_000, 997_000, 1_000_000, 1_000_000),
+    // Large reserves: `k` is near 4e36, well past u64
+    (
+        true,
+        997_000_000_000_000_000,
+        4_000_000_000_000_000_000,
+        1_000_000_000_000_000_000,
+        2_000_000_000_000_000_000,
+    ),
+];
+
+/// An exact-input swap with the expected output as its minimum.
+fn swap(pool: &Pool, user: &User, is_x: bool, amount_in: u64, min_out: u64) -> Instruction {
+    let mut data = vec![3, 5, is_x as u8];
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_out.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(user.key, true),
+            AccountMeta::new_readonly(pool.mint_lp, false),
+            AccountMeta::new(user.x_ata, false),
+            AccountMeta::new(user.y_ata, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn swap_vectors() {
+    let mollusk = mollusk();
+    for &(is_x, x, y, amount_in, amount_out) in SWAP_VECTORS {
+        let (pool, user, accounts) = setup(x, y, MINIMUM_LIQUIDITY, 0);
+
+        let result = run(
+            &mollusk,
+            &swap(&pool, &user, is_x, amount_in, amount_out),
+            &accounts,
+        );
+
+        // The whole input, fee included, stays in the pool
+        let (vault_in, vault_out, user_in, user_out, reserve_in, reserve_out) = match is_x {
+            true => (pool.vault_x, pool.vault_y, user.x_ata, user.y_ata, x, y),
+            false => (pool.vault_y, pool.vault_x, user.y_ata, user.x_ata, y, x),
+        };
+        assert_eq!(amount(&result, &vault_in), reserve_in + amount_in);
+        assert_eq!(
+            amount(&result, &vault_out),
+            reserve_out - amount_out,
+            "output for {amount_in} in"
+        );
+        assert_eq!(amount(&result, &user_in), BALANCE - amount_in);
+        assert_eq!(amount(&result, &user_out), BALANCE + amount_out);
+    }
+}
@@ -0,0 +1,318 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, MINIMUM_LIQUIDITY};
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const BALANCE: u64 = 500_000;
+
+struct Provider {
+    user: Pubkey,
+    user_x_ata: Pubkey,
+    user_y_ata: Pubkey,
+    user_lp_ata: Pubkey,
+}
+
+/// A pool holding `reserve` of each side and as LP supply, and a provider
+/// with `BALANCE` of X and Y and an empty LP ATA.
+fn setup(reserve: u64) -> (Pool, Provider, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = Pubkey::new_unique();
+    let provider = Provider {
+        user,
+        user_x_ata: associated_token_address(&user, &pool.mint_x),
+        user_y_ata: associated_token_address(&user, &pool.mint_y),
+        user_lp_ata: associated_token_address(&user, &pool.mint_lp),
+    };
+
+    let mut accounts = pool.accounts(reserve, |_| {});
+    accounts.push((user, system_account(1_000_000_000)));
+    accounts.push((
+        provider.user_x_ata,
+        token_account(&pool.mint_x, &user, BALANCE),
+    ));
+    accounts.push((
+        provider.user_y_ata,
+        token_account(&pool.mint_y, &user, BALANCE),
+    ));
+    accounts.push((provider.user_lp_ata, token_account(&pool.mint_lp, &user, 0)));
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+    (pool, provider, accounts)
+}
+
+/// Deposits for `amount` LP, paying at most `BALANCE` of either side.
+fn deposit(pool: &Pool, provider: &Provider, amount: u64, trailing: &[AccountMeta]) -> Instruction {
+    deposit_bounded(pool, provider, amount, 0, trailing)
+}
+
+/// As `deposit`, requiring at least `min_lp_out` LP to be minted.
+fn deposit_bounded(
+    pool: &Pool,
+    provider: &Provider,
+    amount: u64,
+    min_lp_out: u64,
+    trailing: &[AccountMeta],
+) -> Instruction {
+    let mut data = vec![1, 2];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&min_lp_out.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(provider.user, true),
+        AccountMeta::new(pool.mint_lp, false),
+        AccountMeta::new(pool.vault_x, false),
+        AccountMeta::new(pool.vault_y, false),
+        AccountMeta::new(provider.user_x_ata, false),
+        AccountMeta::new(provider.user_y_ata, false),
+        AccountMeta::new(provider.user_lp_ata, false),
+        AccountMeta::new(pool.config, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+    ];
+    metas.extend_from_slice(trailing);
+    Instruction::new_with_bytes(PROGRAM_ID, &data, metas)
+}
+
+#[test]
+fn deposit_mints_the_requested_lp_for_a_proportional_share() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup(RESERVE);
+
+    // A tenth of the supply costs a tenth of each reserve
+    let result = mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider, RESERVE / 10, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        RESERVE / 10
+    );
+    assert_eq!(
+        token_amount(result.get_account(&pool.vault_x).unwrap()),
+        RESERVE + RESERVE / 10
+    );
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_y_ata).unwrap()),
+        BALANCE - RESERVE / 10
+    );
+}
+
+#[test]
+fn deposit_beyond_the_users_balance_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup(RESERVE);
+
+    let user_x_ata = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &provider.user_x_ata)
+        .unwrap();
+    user_x_ata.1 = token_account(&pool.mint_x, &provider.user, RESERVE / 10 - 1);
+
+    mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider, RESERVE / 10, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InsufficientX as u32,
+        ))],
+    );
+}
+
+#[test]
+fn deposit_short_on_y_names_the_y_side() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup(RESERVE);
+
+    // X alone covers the deposit, so only Y can be the short side
+    set_account(
+        &mut accounts,
+        &provider.user_y_ata,
+        token_account(&pool.mint_y, &provider.user, RESERVE / 10 - 1),
+    );
+
+    mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider, RESERVE / 10, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InsufficientY as u32,
+        ))],
+    );
+}
+
+#[test]
+fn first_deposit_absorbs_a_donation_made_before_any_lp() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup(0);
+    let donation = 5_000;
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, donation),
+    );
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    accounts.push((locked_lp_ata, system_account(0)));
+
+    // The first depositor sets the price with their maximums and takes the
+    // donation with it, less the locked minimum
+    let result = mollusk.process_and_validate_instruction(
+        &deposit(
+            &pool,
+            &provider,
+            RESERVE,
+            &[AccountMeta::new(locked_lp_ata, false)],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        RESERVE - MINIMUM_LIQUIDITY
+    );
+    assert_eq!(
+        token_amount(result.get_account(&locked_lp_ata).unwrap()),
+        MINIMUM_LIQUIDITY
+    );
+    assert_eq!(
+        token_amount(result.get_account(&pool.vault_x).unwrap()),
+        donation + BALANCE
+    );
+    assert_eq!(
+        token_amount(result.get_account(&pool.vault_y).unwrap()),
+        BALANCE
+    );
+}
+
+#[test]
+fn first_deposit_finds_the_locked_lp_ata_anywhere_in_the_trailing_accounts() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup(0);
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    let unrelated = Pubkey::new_unique();
+    accounts.push((locked_lp_ata, system_account(0)));
+    accounts.push((unrelated, system_account(1_000_000)));
+
+    // The locked ATA is matched by its derived address, not its position
+    let result = mollusk.process_and_validate_instruction(
+        &deposit(
+            &pool,
+            &provider,
+            RESERVE,
+            &[
+                AccountMeta::new(locked_lp_ata, false),
+                AccountMeta::new_readonly(unrelated, false),
+            ],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&locked_lp_ata).unwrap()),
+        MINIMUM_LIQUIDITY
+    );
+}
+
+#[test]
+fn first_deposit_at_or_below_the_locked_minimum_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup(0);
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    accounts.push((locked_lp_ata, system_account(0)));
+
+    mollusk.process_and_validate_instruction(
+        &deposit(
+            &pool,
+            &provider,
+            MINIMUM_LIQUIDITY,
+            &[AccountMeta::new(locked_lp_ata, false)],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::BelowMinimumLiquidity as u32,
+        ))],
+    );
+}
+
+#[test]
+fn deposit_into_reserves_near_u64_max_fits() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup(u64::MAX / 2);
+
+    let result = mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider, 1_000, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        1_000
+    );
+}
+
+#[test]
+fn deposit_overflowing_the_reserves_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup(u64::MAX - 10);
+
+    mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider, 1_000, &[]),
+        &accounts,
+        &[Check::err(ProgramError::ArithmeticOverflow)],
+    );
+}
+
+#[test]
+fn deposit_minting_exactly_min_lp_out_succeeds() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup(RESERVE);
+
+    let result = mollusk.process_and_validate_instruction(
+        &deposit_bounded(&pool, &provider, RESERVE / 10, RESERVE / 10, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        RESERVE / 10
+    );
+}
+
+#[test]
+fn deposit_into_a_pool_drained_since_the_quote_is_held_to_min_lp_out() {
+    let mollusk = mollusk();
+    // Quoted against a live pool, but every LP exited before execution, so
+    // the deposit now pays the locked minimum out of its LP
+    let (pool, provider, mut accounts) = setup(0);
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    accounts.push((locked_lp_ata, system_account(0)));
+
+    mollusk.process_and_validate_instruction(
+        &deposit_bounded(
+            &pool,
+            &provider,
+            RESERVE / 10,
+            RESERVE / 10,
+            &[AccountMeta::new(locked_lp_ata, false)],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::SlippageExceeded as u32,
+        ))],
+    );
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState};
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const LP_AMOUNT: u64 = 100_000;
+const START: i64 = 1_700_000_000;
+const GRACE: i64 = 600;
+
+fn disable(pool: &Pool, authority: &Pubkey) -> Instruction {
+    let mut data = vec![5, 1];
+    data.extend_from_slice(&GRACE.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// Exits the provider's whole LP balance, so no minimums are needed.
+fn withdraw_all(pool: &Pool, provider: &User) -> Instruction {
+    pool.withdraw(
+        provider,
+        WithdrawData {
+            amount: LP_AMOUNT,
+            ..Default::default()
+        },
+    )
+}
+
+/// A pool the returned authority disabled at `START`, and a provider
+/// holding LP in it.
+fn disabled() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let provider = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+    accounts.extend(provider.accounts(&pool, 0, 0, LP_AMOUNT));
+
+    let result = mollusk_at(START).process_and_validate_instruction(
+        &disable(&pool, &authority),
+        &accounts,
+        &[Check::success()],
+    );
+    let config = config_of(result.get_account(&pool.config).unwrap());
+    assert_eq!(config.state(), AmmState::Disabled as u8);
+    assert_eq!(config.disabled_at(), START);
+    assert_eq!(config.grace_seconds(), GRACE);
+
+    apply(&mut accounts, &result);
+    (pool, provider, accounts)
+}
+
+#[test]
+fn disabled_pool_allows_withdraws_during_the_grace_period() {
+    let (pool, provider, accounts) = disabled();
+
+    let result = mollusk_at(START + GRACE - 1).process_and_validate_instruction(
+        &withdraw_all(&pool, &provider),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &provider.lp_ata), 0);
+    assert_eq!(amount_at(&result, &provider.x_ata), LP_AMOUNT);
+    assert_eq!(amount_at(&result, &provider.y_ata), LP_AMOUNT);
+}
+
+#[test]
+fn disabled_pool_rejects_withdraws_once_the_grace_period_is_over() {
+    let (pool, provider, accounts) = disabled();
+
+    mollusk_at(START + GRACE).process_and_validate_instruction(
+        &withdraw_all(&pool, &provider),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InvalidState as u32,
+        ))],
+    );
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState};
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const DONATION_X: u64 = 100_000;
+const DONATION_Y: u64 = 50_000;
+
+fn donate(pool: &Pool, donor: &User) -> Instruction {
+    let mut data = vec![29, 1];
+    data.extend_from_slice(&DONATION_X.to_le_bytes());
+    data.extend_from_slice(&DONATION_Y.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(donor.key, true),
+            AccountMeta::new(donor.x_ata, false),
+            AccountMeta::new(donor.y_ata, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new_readonly(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+/// A pool with `RESERVE` of each side and as LP supply, and a donor
+/// holding exactly the donation.
+fn setup(state: AmmState) -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let donor = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_state(state as u8).unwrap();
+    });
+    accounts.extend(donor.accounts(&pool, DONATION_X, DONATION_Y, 0));
+    (pool, donor, accounts)
+}
+
+#[test]
+fn donate_grows_the_reserves_behind_every_lp_share() {
+    let mollusk = mollusk();
+    let (pool, donor, accounts) = setup(AmmState::Initialized);
+
+    let result = mollusk.process_and_validate_instruction(
+        &donate(&pool, &donor),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(amount_at(&result, &pool.vault_x), RESERVE + DONATION_X);
+    assert_eq!(amount_at(&result, &pool.vault_y), RESERVE + DONATION_Y);
+    assert_eq!(amount_at(&result, &donor.x_ata), 0);
+    assert_eq!(amount_at(&result, &donor.y_ata), 0);
+
+    // Donate never takes the LP mint, so the supply stays at RESERVE while
+    // each share now redeems for more of both sides
+    let per_share = |reserve: u64| reserve as u128 * 1_000_000 / RESERVE as u128;
+    assert!(per_share(amount_at(&result, &pool.vault_x)) > per_share(RESERVE));
+    assert!(per_share(amount_at(&result, &pool.vault_y)) > per_share(RESERVE));
+}
+
+#[test]
+fn donate_rejects_a_pool_that_is_not_trading() {
+    let mollusk = mollusk();
+    let (pool, donor, accounts) = setup(AmmState::WithdrawOnly);
+
+    mollusk.process_and_validate_instruction(
+        &donate(&pool, &donor),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InvalidState as u32,
+        ))],
+    );
+}
+
+#[test]
+fn donate_rejects_a_vault_outside_the_pool() {
+    let mollusk = mollusk();
+    let (pool, donor, mut accounts) = setup(AmmState::Initialized);
+    let impostor = Pubkey::new_unique();
+    accounts.push((impostor, token_account(&pool.mint_x, &donor.key, 0)));
+
+    let mut instruction = donate(&pool, &donor);
+    instruction.accounts[3].pubkey = impostor;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InvalidVault as u32,
+        ))],
+    );
+}
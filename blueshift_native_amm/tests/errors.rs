@@ -0,0 +1,56 @@
+//! Decoding custom error codes back into `AmmError`, as clients do. Run
+//! with `--features client`.
+
+#![cfg(feature = "client")]
+
+use blueshift_native_amm::AmmError;
+use pinocchio::program_error::ProgramError;
+
+const VARIANTS: [AmmError; 29] = [
+    AmmError::CurveError,
+    AmmError::OutputNotAllowed,
+    AmmError::InsufficientX,
+    AmmError::InsufficientY,
+    AmmError::PoolInconsistent,
+    AmmError::UnsupportedMint,
+    AmmError::ReserveBelowFloor,
+    AmmError::LaunchProtection,
+    AmmError::TransferMismatch,
+    AmmError::TooManyItems,
+    AmmError::UnsupportedCurve,
+    AmmError::PoolNotInitialized,
+    AmmError::FlashLoanActive,
+    AmmError::FlashLoanNotRepaid,
+    AmmError::CurveInit,
+    AmmError::SwapFailed,
+    AmmError::SlippageExceeded,
+    AmmError::InvalidState,
+    AmmError::InvalidVault,
+    AmmError::VersionMismatch,
+    AmmError::SunsetPending,
+    AmmError::AlreadyInitialized,
+    AmmError::NativeSolUnsupported,
+    AmmError::ReferralTooHigh,
+    AmmError::ZeroTrade,
+    AmmError::FeeSplitExceeded,
+    AmmError::OracleDivergence,
+    AmmError::DecimalsSpreadTooWide,
+    AmmError::BelowMinimumLiquidity,
+];
+
+#[test]
+fn every_variant_round_trips_through_its_custom_code() {
+    for variant in VARIANTS {
+        let ProgramError::Custom(code) = ProgramError::from(variant) else {
+            panic!("{variant:?} did not convert to a custom error");
+        };
+        assert_eq!(AmmError::try_from(code), Ok(variant));
+    }
+}
+
+#[test]
+fn unassigned_codes_are_rejected() {
+    for code in [0, 5, 31, u32::MAX] {
+        assert_eq!(AmmError::try_from(code), Err(ProgramError::InvalidArgument));
+    }
+}
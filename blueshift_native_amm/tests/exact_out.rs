@@ -0,0 +1,110 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, program_error::ProgramError, pubkey::Pubkey};
+
+/// At 30 bps, buying 750_000 from 1_000_000 against 997_000 costs exactly
+/// 3_000_000: 2_991_000 after the fee, the amount that leaves `k` whole.
+const RESERVE_IN: u64 = 997_000;
+const RESERVE_OUT: u64 = 1_000_000;
+const AMOUNT_OUT: u64 = 750_000;
+const AMOUNT_IN: u64 = 3_000_000;
+
+/// A pool holding `RESERVE_IN` on the side a trader selling X (`is_x`) or Y
+/// pays into, and a trader holding `AMOUNT_IN` of that side.
+fn setup(is_x: bool) -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let (vault_in, mint_in) = match is_x {
+        true => (pool.vault_x, pool.mint_x),
+        false => (pool.vault_y, pool.mint_y),
+    };
+    let mut accounts = pool.accounts(RESERVE_OUT, |_| {});
+    set_account(
+        &mut accounts,
+        &vault_in,
+        token_account(&mint_in, &pool.config, RESERVE_IN),
+    );
+    let (x, y) = match is_x {
+        true => (AMOUNT_IN, 0),
+        false => (0, AMOUNT_IN),
+    };
+    accounts.extend(trader.accounts(&pool, x, y, 0));
+    (pool, trader, accounts)
+}
+
+#[test]
+fn exact_out_buys_y_for_the_computed_x() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(true);
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_out(true, AMOUNT_OUT, AMOUNT_IN),
+            &[],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &trader.x_ata), 0);
+    assert_eq!(amount_at(&result, &trader.y_ata), AMOUNT_OUT);
+    assert_eq!(amount_at(&result, &pool.vault_x), RESERVE_IN + AMOUNT_IN);
+}
+
+#[test]
+fn exact_out_buys_x_for_the_computed_y() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(false);
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_out(false, AMOUNT_OUT, AMOUNT_IN),
+            &[],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &trader.y_ata), 0);
+    assert_eq!(amount_at(&result, &trader.x_ata), AMOUNT_OUT);
+    assert_eq!(amount_at(&result, &pool.vault_y), RESERVE_IN + AMOUNT_IN);
+}
+
+#[test]
+fn exact_out_rejects_a_max_input_below_the_cost() {
+    let mollusk = mollusk();
+
+    for is_x in [true, false] {
+        let (pool, trader, accounts) = setup(is_x);
+        mollusk.process_and_validate_instruction(
+            &pool.swap(
+                &trader,
+                SwapData::exact_out(is_x, AMOUNT_OUT, AMOUNT_IN - 1),
+                &[],
+            ),
+            &accounts,
+            &[Check::err(ProgramError::Custom(
+                AmmError::SlippageExceeded as u32,
+            ))],
+        );
+    }
+}
+
+#[test]
+fn exact_out_rejects_buying_the_whole_reserve() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(true);
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_out(true, RESERVE_OUT, u64::MAX),
+            &[],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT: u64 = 100_000;
+const NOW: i64 = 1_700_000_000;
+
+/// A pool and a user holding `AMOUNT` of X, Y and LP.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(user.accounts(&pool, AMOUNT, AMOUNT, AMOUNT));
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+    (pool, user, accounts)
+}
+
+/// A swap, a deposit and a withdraw, each due by `expiration`.
+fn instructions(pool: &Pool, user: &User, expiration: i64) -> [(&'static str, Instruction); 3] {
+    [
+        (
+            "swap",
+            pool.swap(
+                user,
+                SwapData {
+                    expiration,
+                    ..SwapData::exact_in(true, AMOUNT, 1)
+                },
+                &[],
+            ),
+        ),
+        (
+            "deposit",
+            pool.deposit(
+                user,
+                DepositData {
+                    amount: AMOUNT,
+                    max_x: AMOUNT,
+                    max_y: AMOUNT,
+                    expiration,
+                    ..Default::default()
+                },
+                &[],
+            ),
+        ),
+        (
+            "withdraw",
+            pool.withdraw(
+                user,
+                WithdrawData {
+                    amount: AMOUNT,
+                    expiration,
+                    ..Default::default()
+                },
+            ),
+        ),
+    ]
+}
+
+#[test]
+fn zero_expiration_sets_no_deadline() {
+    let mollusk = mollusk_at(NOW);
+    let (pool, user, accounts) = setup();
+
+    for (name, instruction) in instructions(&pool, &user, 0) {
+        let result = mollusk.process_instruction(&instruction, &accounts);
+        assert!(result.program_result.is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn future_expiration_passes() {
+    let mollusk = mollusk_at(NOW);
+    let (pool, user, accounts) = setup();
+
+    for (name, instruction) in instructions(&pool, &user, NOW + 60) {
+        let result = mollusk.process_instruction(&instruction, &accounts);
+        assert!(result.program_result.is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn past_expiration_is_rejected() {
+    let mollusk = mollusk_at(NOW);
+    let (pool, user, accounts) = setup();
+
+    for (_, instruction) in instructions(&pool, &user, NOW - 1) {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+}
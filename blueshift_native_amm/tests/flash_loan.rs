@@ -0,0 +1,107 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+/// What the open loan needs the vault back at: the reserve plus a fee.
+const REQUIRED: u64 = RESERVE + 10_000;
+
+fn flash_repay(
+    borrower: &Pubkey,
+    borrower_ata: &Pubkey,
+    pool: &Pool,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[28],
+        vec![
+            AccountMeta::new(*borrower, true),
+            AccountMeta::new(*borrower_ata, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// A pool with a loan out of vault X still owing `REQUIRED - RESERVE`, and
+/// a borrower holding enough X to settle it.
+fn setup() -> (Pool, Pubkey, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let borrower = Pubkey::new_unique();
+    let borrower_ata = associated_token_address(&borrower, &pool.mint_x);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.open_flash_loan(true, REQUIRED);
+    });
+    accounts.push((borrower, system_account(1_000_000_000)));
+    accounts.push((borrower_ata, token_account(&pool.mint_x, &borrower, 50_000)));
+    (pool, borrower, borrower_ata, accounts)
+}
+
+#[test]
+fn flash_repay_tops_up_the_vault_and_closes_the_loan() {
+    let mollusk = mollusk();
+    let (pool, borrower, borrower_ata, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &flash_repay(&borrower, &borrower_ata, &pool, &TOKEN_PROGRAM_ID),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&pool.vault_x).unwrap()),
+        REQUIRED
+    );
+    assert_eq!(
+        token_amount(result.get_account(&borrower_ata).unwrap()),
+        50_000 - (REQUIRED - RESERVE)
+    );
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).flash_required(),
+        0
+    );
+}
+
+#[test]
+fn flash_repay_rejects_a_substitute_token_program() {
+    let mollusk = mollusk();
+    let (pool, borrower, borrower_ata, mut accounts) = setup();
+    let fake_token_program = Pubkey::new_unique();
+    accounts.push((fake_token_program, system_account(0)));
+
+    mollusk.process_and_validate_instruction(
+        &flash_repay(&borrower, &borrower_ata, &pool, &fake_token_program),
+        &accounts,
+        &[Check::err(ProgramError::IncorrectProgramId)],
+    );
+}
+
+#[test]
+fn flash_repay_rejects_a_vault_the_pool_does_not_own() {
+    let mollusk = mollusk();
+    let (pool, borrower, borrower_ata, mut accounts) = setup();
+
+    // Right address, but the token account belongs to the borrower
+    let vault = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &pool.vault_x)
+        .unwrap();
+    vault.1 = token_account(&pool.mint_x, &borrower, REQUIRED);
+
+    mollusk.process_and_validate_instruction(
+        &flash_repay(&borrower, &borrower_ata, &pool, &TOKEN_PROGRAM_ID),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            blueshift_native_amm::AmmError::InvalidVault as u32,
+        ))],
+    );
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE_X: u64 = 4_000_000_000_000;
+const RESERVE_Y: u64 = 3_000_000_000_000;
+
+fn get_invariant(pool: &Pool, vault_x: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[17],
+        vec![
+            AccountMeta::new_readonly(*vault_x, false),
+            AccountMeta::new_readonly(pool.vault_y, false),
+            AccountMeta::new_readonly(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn get_invariant_returns_the_product_of_the_vaults() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let mut accounts = pool.accounts(RESERVE_X, |_| {});
+    set_account(
+        &mut accounts,
+        &pool.vault_y,
+        token_account(&pool.mint_y, &pool.config, RESERVE_Y),
+    );
+
+    // The product overflows a u64, so only a u128 holds it
+    let k = RESERVE_X as u128 * RESERVE_Y as u128;
+    assert!(k > u64::MAX as u128);
+    mollusk.process_and_validate_instruction(
+        &get_invariant(&pool, &pool.vault_x),
+        &accounts,
+        &[Check::success(), Check::return_data(&k.to_le_bytes())],
+    );
+}
+
+#[test]
+fn get_invariant_rejects_a_vault_outside_the_pool() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let mut accounts = pool.accounts(RESERVE_X, |_| {});
+    let impostor = Pubkey::new_unique();
+    accounts.push((
+        impostor,
+        token_account(&pool.mint_x, &pool.config, RESERVE_X * 2),
+    ));
+
+    mollusk.process_and_validate_instruction(
+        &get_invariant(&pool, &impostor),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
@@ -0,0 +1,259 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState, MINIMUM_LIQUIDITY};
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+fn initialize(pool: &Pool, initializer: &Pubkey, trailing: &[AccountMeta]) -> Instruction {
+    let mut data = vec![0, 3];
+    data.extend_from_slice(&pool.initialize_data());
+    let mut metas = vec![
+        AccountMeta::new(*initializer, true),
+        AccountMeta::new(pool.mint_lp, false),
+        AccountMeta::new(pool.config, false),
+        AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(pool.mint_x, false),
+        AccountMeta::new_readonly(pool.mint_y, false),
+        AccountMeta::new_readonly(pool.vault_x, false),
+        AccountMeta::new_readonly(pool.vault_y, false),
+    ];
+    metas.extend_from_slice(trailing);
+    Instruction::new_with_bytes(PROGRAM_ID, &data, metas)
+}
+
+/// A pool that doesn't exist yet, with vaults nobody has created, and a
+/// funded initializer.
+fn setup() -> (Pool, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let initializer = Pubkey::new_unique();
+    let accounts = vec![
+        (initializer, system_account(10_000_000_000)),
+        (pool.mint_lp, system_account(0)),
+        (pool.config, system_account(0)),
+        keyed_account_for_system_program(),
+        mollusk_svm_programs_token::token::keyed_account(),
+        (pool.mint_x, mint(u64::MAX, 6)),
+        (pool.mint_y, mint(u64::MAX, 9)),
+        (pool.vault_x, system_account(0)),
+        (pool.vault_y, system_account(0)),
+    ];
+    (pool, initializer, accounts)
+}
+
+#[test]
+fn initialize_creates_the_config_and_lp_mint() {
+    let mollusk = mollusk();
+    let (pool, initializer, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let config = config_of(result.get_account(&pool.config).unwrap());
+    assert_eq!(config.state(), AmmState::Initialized as u8);
+    assert_eq!(config.mint_x(), &pool.mint_x.to_bytes());
+    assert_eq!(config.fee(), Pool::FEE_BPS);
+    assert_eq!(
+        result.get_account(&pool.mint_lp).unwrap().owner,
+        TOKEN_PROGRAM_ID
+    );
+}
+
+#[test]
+fn initialize_rejects_a_token_2022_pool_mint() {
+    let mollusk = mollusk();
+    let (pool, initializer, mut accounts) = setup();
+
+    let mint_y = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &pool.mint_y)
+        .unwrap();
+    mint_y.1.owner = TOKEN_2022_PROGRAM_ID;
+
+    mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::UnsupportedMint as u32,
+        ))],
+    );
+}
+
+#[test]
+fn initialize_mints_lp_against_consistently_pre_seeded_vaults() {
+    let mollusk = mollusk();
+    let (pool, initializer, mut accounts) = setup();
+    let seeded = 1_000_000;
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, seeded),
+    );
+    set_account(
+        &mut accounts,
+        &pool.vault_y,
+        token_account(&pool.mint_y, &pool.config, seeded),
+    );
+    let initializer_lp_ata = associated_token_address(&initializer, &pool.mint_lp);
+    let locked_lp_ata = associated_token_address(&pool.config, &pool.mint_lp);
+    accounts.push((initializer_lp_ata, system_account(0)));
+    accounts.push((locked_lp_ata, system_account(0)));
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize(
+            &pool,
+            &initializer,
+            &[
+                AccountMeta::new(initializer_lp_ata, false),
+                AccountMeta::new(locked_lp_ata, false),
+                AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+            ],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // sqrt(x * y) in total, with the minimum locked in the config's ATA
+    assert_eq!(
+        token_amount(result.get_account(&initializer_lp_ata).unwrap()),
+        seeded - MINIMUM_LIQUIDITY
+    );
+    assert_eq!(
+        token_amount(result.get_account(&locked_lp_ata).unwrap()),
+        MINIMUM_LIQUIDITY
+    );
+}
+
+#[test]
+fn initialize_rejects_a_one_sided_pre_seeded_pool() {
+    let mollusk = mollusk();
+    let (pool, initializer, mut accounts) = setup();
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, 1_000_000),
+    );
+
+    mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::PoolInconsistent as u32,
+        ))],
+    );
+}
+
+#[test]
+fn initialize_rejects_a_vault_other_than_the_derived_one() {
+    let mollusk = mollusk();
+    let (mut pool, initializer, mut accounts) = setup();
+    let vault_y = Pubkey::new_unique();
+    accounts.push((vault_y, system_account(0)));
+    pool.vault_y = vault_y;
+
+    mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InvalidVault as u32,
+        ))],
+    );
+}
+
+/// Initializes a pool whose mints carry `x_decimals` and `y_decimals`,
+/// allowing a spread of at most `max_spread`.
+fn initialize_with_decimals(
+    x_decimals: u8,
+    y_decimals: u8,
+    max_spread: u8,
+) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let (pool, initializer, mut accounts) = setup();
+    set_account(&mut accounts, &pool.mint_x, mint(u64::MAX, x_decimals));
+    set_account(&mut accounts, &pool.mint_y, mint(u64::MAX, y_decimals));
+    let mut instruction = initialize(&pool, &initializer, &[]);
+    *instruction.data.last_mut().unwrap() = max_spread;
+    (instruction, accounts)
+}
+
+#[test]
+fn initialize_accepts_decimals_within_the_spread() {
+    let mollusk = mollusk();
+
+    // Matching decimals, and a 0/9 pair right at the default spread
+    for (x_decimals, y_decimals) in [(6, 6), (0, 9)] {
+        let (instruction, accounts) = initialize_with_decimals(x_decimals, y_decimals, 9);
+        mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+    }
+}
+
+#[test]
+fn initialize_rejects_decimals_beyond_the_spread() {
+    let mollusk = mollusk();
+
+    // Past the default spread, and past a tighter one the initializer set
+    for (x_decimals, y_decimals, max_spread) in [(0, 10, 9), (6, 9, 2)] {
+        let (instruction, accounts) = initialize_with_decimals(x_decimals, y_decimals, max_spread);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(ProgramError::Custom(
+                AmmError::DecimalsSpreadTooWide as u32,
+            ))],
+        );
+    }
+}
+
+#[test]
+fn initialize_twice_rejects_the_second_call() {
+    let mollusk = mollusk();
+    let (pool, initializer, mut accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    apply(&mut accounts, &result);
+
+    mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::AlreadyInitialized as u32,
+        ))],
+    );
+}
+
+#[test]
+fn initialize_creates_a_pool_whose_addresses_were_funded_first() {
+    let mollusk = mollusk();
+    let (pool, initializer, mut accounts) = setup();
+
+    // Lamports sent ahead to block `CreateAccount` are kept, not refused
+    set_account(&mut accounts, &pool.config, system_account(1));
+    set_account(&mut accounts, &pool.mint_lp, system_account(1));
+
+    let result = mollusk.process_and_validate_instruction(
+        &initialize(&pool, &initializer, &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let config = result.get_account(&pool.config).unwrap();
+    assert_eq!(config.owner, PROGRAM_ID);
+    assert_eq!(config_of(config).state(), AmmState::Initialized as u8);
+    assert_eq!(
+        result.get_account(&pool.mint_lp).unwrap().owner,
+        TOKEN_PROGRAM_ID
+    );
+}
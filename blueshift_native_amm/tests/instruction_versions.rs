@@ -0,0 +1,120 @@
+//! Each instruction's data opens with a version byte, and older layouts keep
+//! parsing with the fields they lack defaulted. These tests run natively,
+//! with no deadline set so the clock is never read.
+
+use blueshift_native_amm::{
+    DepositInstructionData, InitializeInstructionData, WithdrawInstructionData,
+};
+use pinocchio::program_error::ProgramError;
+
+fn deposit_data(version: u8, min_lp_out: Option<u64>) -> Vec<u8> {
+    let mut data = vec![version];
+    data.extend_from_slice(&100u64.to_le_bytes()); // amount
+    data.extend_from_slice(&200u64.to_le_bytes()); // max_x
+    data.extend_from_slice(&300u64.to_le_bytes()); // max_y
+    data.extend_from_slice(&0i64.to_le_bytes()); // expiration
+    if let Some(min_lp_out) = min_lp_out {
+        data.extend_from_slice(&min_lp_out.to_le_bytes());
+    }
+    data
+}
+
+fn withdraw_data(version: u8, allow_zero_slippage: Option<u8>) -> Vec<u8> {
+    let mut data = vec![version];
+    data.extend_from_slice(&100u64.to_le_bytes()); // amount
+    data.extend_from_slice(&1u64.to_le_bytes()); // min_x
+    data.extend_from_slice(&2u64.to_le_bytes()); // min_y
+    data.extend_from_slice(&0i64.to_le_bytes()); // expiration
+    data.extend(allow_zero_slippage);
+    data
+}
+
+/// v1 Initialize data, up to and including the first `authority_len` bytes
+/// of a trailing authority.
+fn initialize_v1_data(authority_len: usize) -> Vec<u8> {
+    let mut data = vec![1];
+    data.extend_from_slice(&1u64.to_le_bytes()); // seed
+    data.extend_from_slice(&30u16.to_le_bytes()); // fee
+    data.extend_from_slice(&[1; 32]); // mint_x
+    data.extend_from_slice(&[2; 32]); // mint_y
+    data.extend_from_slice(&[254, 253]); // config_bump, lp_bump
+    data.extend_from_slice(&0u64.to_le_bytes()); // min_lp_mint
+    data.extend_from_slice(&[0; 32]); // protocol_fee_recipient
+    data.extend_from_slice(&0u64.to_le_bytes()); // min_reserve_x
+    data.extend_from_slice(&0u64.to_le_bytes()); // min_reserve_y
+    data.push(0); // soulbound_lp
+    data.extend_from_slice(&0i64.to_le_bytes()); // launch_window
+    data.extend_from_slice(&0u16.to_le_bytes()); // launch_max_input_bps
+    data.extend_from_slice(&0u16.to_le_bytes()); // max_referral_bps
+    data.push(0); // curve_type
+    data.extend_from_slice(&[9; 32][..authority_len]);
+    data
+}
+
+#[test]
+fn deposit_parses_both_layouts() {
+    let v1 = DepositInstructionData::try_from(&deposit_data(1, None)[..]).unwrap();
+    let (amount, max_x, max_y, min_lp_out) = (v1.amount, v1.max_x, v1.max_y, v1.min_lp_out);
+    assert_eq!((amount, max_x, max_y, min_lp_out), (100, 200, 300, 0));
+
+    let v2 = DepositInstructionData::try_from(&deposit_data(2, Some(50))[..]).unwrap();
+    let (amount, min_lp_out) = (v2.amount, v2.min_lp_out);
+    assert_eq!((amount, min_lp_out), (100, 50));
+}
+
+#[test]
+fn withdraw_parses_both_layouts() {
+    let v1 = WithdrawInstructionData::try_from(&withdraw_data(1, None)[..]).unwrap();
+    let (min_x, min_y, allow_zero_slippage) = (v1.min_x, v1.min_y, v1.allow_zero_slippage);
+    assert_eq!((min_x, min_y, allow_zero_slippage), (1, 2, 0));
+
+    let v2 = WithdrawInstructionData::try_from(&withdraw_data(2, Some(1))[..]).unwrap();
+    let allow_zero_slippage = v2.allow_zero_slippage;
+    assert_eq!(allow_zero_slippage, 1);
+}
+
+#[test]
+fn unknown_versions_are_rejected() {
+    for version in [0, 3, u8::MAX] {
+        assert!(matches!(
+            DepositInstructionData::try_from(&deposit_data(version, Some(50))[..]),
+            Err(ProgramError::InvalidInstructionData)
+        ));
+        assert!(matches!(
+            WithdrawInstructionData::try_from(&withdraw_data(version, Some(1))[..]),
+            Err(ProgramError::InvalidInstructionData)
+        ));
+    }
+}
+
+#[test]
+fn a_layout_sent_under_the_other_version_is_rejected() {
+    assert!(matches!(
+        DepositInstructionData::try_from(&deposit_data(1, Some(50))[..]),
+        Err(ProgramError::InvalidInstructionData)
+    ));
+    assert!(matches!(
+        WithdrawInstructionData::try_from(&withdraw_data(2, None)[..]),
+        Err(ProgramError::InvalidInstructionData)
+    ));
+}
+
+#[test]
+fn initialize_v1_parses_a_full_or_absent_authority() {
+    let with = InitializeInstructionData::try_from(&initialize_v1_data(32)[..]).unwrap();
+    assert_eq!(with.authority, [9; 32]);
+
+    let without = InitializeInstructionData::try_from(&initialize_v1_data(0)[..]).unwrap();
+    assert_eq!(without.authority, [0; 32]);
+}
+
+#[test]
+fn initialize_v1_rejects_a_partial_authority() {
+    for authority_len in [1, 16, 31] {
+        assert_eq!(
+            InitializeInstructionData::try_from(&initialize_v1_data(authority_len)[..]).err(),
+            Some(ProgramError::InvalidInstructionData),
+            "{authority_len}"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, program_error::ProgramError, pubkey::Pubkey};
+
+/// At 30 bps, 3_000_000 X into 997_000/1_000_000 buys exactly 750_000 Y,
+/// pricing the output on the 2_991_000 left after the 9_000 fee.
+const RESERVE_X: u64 = 997_000;
+const RESERVE_Y: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 3_000_000;
+const AMOUNT_OUT: u64 = 750_000;
+const FEE: u64 = 9_000;
+
+/// A pool with no protocol fee, and a trader holding `AMOUNT_IN` X.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE_Y, |_| {});
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, RESERVE_X),
+    );
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+    (pool, trader, accounts)
+}
+
+#[test]
+fn swap_grows_k_by_exactly_the_fee_portion() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // The whole input lands in the vault, fee included
+    let reserve_x = amount_at(&result, &pool.vault_x);
+    let reserve_y = amount_at(&result, &pool.vault_y);
+    assert_eq!(reserve_x, RESERVE_X + AMOUNT_IN);
+    assert_eq!(amount_at(&result, &trader.y_ata), AMOUNT_OUT);
+
+    let k_before = RESERVE_X as u128 * RESERVE_Y as u128;
+    let k_after = reserve_x as u128 * reserve_y as u128;
+    assert_eq!(k_after - k_before, FEE as u128 * reserve_y as u128);
+}
+
+#[test]
+fn swap_never_pays_the_fee_portion_to_the_trader() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_in(true, AMOUNT_IN, AMOUNT_OUT + 1),
+            &[],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::SlippageExceeded as u32,
+        ))],
+    );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const LP_AMOUNT: u64 = 100_000;
+/// Position of `mint_lp` in Deposit's and Withdraw's account lists.
+const MINT_LP: usize = 1;
+
+/// A pool and a provider holding `LP_AMOUNT` of each side and of LP.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let provider = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(provider.accounts(&pool, LP_AMOUNT, LP_AMOUNT, LP_AMOUNT));
+    accounts.push(keyed_account_for_system_program());
+    (pool, provider, accounts)
+}
+
+/// Points `instruction` at an LP mint of the same supply outside the pool.
+fn spoof_mint_lp(
+    pool: &Pool,
+    instruction: &mut Instruction,
+    accounts: &mut Vec<(Pubkey, Account)>,
+) {
+    let spoofed = Pubkey::new_unique();
+    accounts.push((spoofed, lp_mint(RESERVE, &pool.config)));
+    instruction.accounts[MINT_LP].pubkey = spoofed;
+}
+
+fn deposit(pool: &Pool, provider: &User) -> Instruction {
+    pool.deposit(
+        provider,
+        DepositData {
+            amount: LP_AMOUNT,
+            max_x: LP_AMOUNT,
+            max_y: LP_AMOUNT,
+            ..Default::default()
+        },
+        &[],
+    )
+}
+
+#[test]
+fn deposit_mints_from_the_pools_own_lp_mint() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &provider.lp_ata), 2 * LP_AMOUNT);
+}
+
+#[test]
+fn deposit_rejects_a_spoofed_lp_mint() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup();
+    let mut instruction = deposit(&pool, &provider);
+    spoof_mint_lp(&pool, &mut instruction, &mut accounts);
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+/// Exits the provider's whole LP balance.
+fn withdraw(pool: &Pool, provider: &User) -> Instruction {
+    pool.withdraw(
+        provider,
+        WithdrawData {
+            amount: LP_AMOUNT,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn withdraw_burns_from_the_pools_own_lp_mint() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &withdraw(&pool, &provider),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(amount_at(&result, &provider.lp_ata), 0);
+    assert_eq!(amount_at(&result, &provider.x_ata), 2 * LP_AMOUNT);
+}
+
+#[test]
+fn withdraw_rejects_a_mismatched_lp_mint() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup();
+    let mut instruction = withdraw(&pool, &provider);
+    spoof_mint_lp(&pool, &mut instruction, &mut accounts);
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn withdraw_rejects_an_lp_mint_outside_the_token_programs() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup();
+    let mut mint_lp = lp_mint(RESERVE, &pool.config);
+    mint_lp.owner = Pubkey::new_unique();
+    set_account(&mut accounts, &pool.mint_lp, mint_lp);
+
+    mollusk.process_and_validate_instruction(
+        &withdraw(&pool, &provider),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
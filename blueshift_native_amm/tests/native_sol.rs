@@ -0,0 +1,129 @@
+mod common;
+
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account, instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT: u64 = 10_000;
+const LAMPORTS: u64 = 1_000_000_000;
+
+/// A SOL/token pool with `RESERVE` of each side, and a user holding
+/// `LAMPORTS`, `AMOUNT` of Y and an empty wrapped SOL account.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::with_mints(NATIVE_MINT, Pubkey::new_unique());
+    let user = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| config.set_native(true, false));
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        native_token_account(&pool.config, RESERVE),
+    );
+    accounts.extend(user.accounts(&pool, 0, AMOUNT, 0));
+    set_account(
+        &mut accounts,
+        &user.x_ata,
+        native_token_account(&user.key, 0),
+    );
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::associated_token::keyed_account());
+    (pool, user, accounts)
+}
+
+fn system_program() -> AccountMeta {
+    AccountMeta::new_readonly(solana_sdk::system_program::ID, false)
+}
+
+fn lamports_of(accounts: &[(Pubkey, Account)], key: &Pubkey) -> u64 {
+    accounts.iter().find(|(k, _)| k == key).unwrap().1.lamports
+}
+
+#[test]
+fn deposit_wraps_the_sol_side_from_lamports() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.deposit(
+            &user,
+            DepositData {
+                amount: AMOUNT,
+                max_x: AMOUNT,
+                max_y: AMOUNT,
+                ..Default::default()
+            },
+            &[],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(amount_at(&result, &pool.vault_x), RESERVE + AMOUNT);
+    assert_eq!(
+        result.get_account(&user.key).unwrap().lamports,
+        LAMPORTS - AMOUNT
+    );
+    assert_eq!(amount_at(&result, &user.x_ata), 0);
+    assert_eq!(amount_at(&result, &user.lp_ata), AMOUNT);
+}
+
+#[test]
+fn swap_sells_sol_from_lamports() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &user,
+            SwapData::exact_in(true, AMOUNT, 1),
+            &[system_program()],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(amount_at(&result, &pool.vault_x), RESERVE + AMOUNT);
+    assert_eq!(
+        result.get_account(&user.key).unwrap().lamports,
+        LAMPORTS - AMOUNT
+    );
+    let out = RESERVE - amount_at(&result, &pool.vault_y);
+    assert!(out > 0);
+    assert_eq!(amount_at(&result, &user.y_ata), AMOUNT + out);
+}
+
+#[test]
+fn swap_buys_sol_as_lamports() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&user, SwapData::exact_in(false, AMOUNT, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // The wrapped SOL account is closed back to the user, rent included
+    let out = RESERVE - amount_at(&result, &pool.vault_x);
+    assert!(out > 0);
+    assert_eq!(result.get_account(&user.x_ata).unwrap().lamports, 0);
+    assert_eq!(
+        result.get_account(&user.key).unwrap().lamports,
+        LAMPORTS + lamports_of(&accounts, &user.x_ata) + out
+    );
+    assert_eq!(amount_at(&result, &user.y_ata), 0);
+}
+
+#[test]
+fn swap_selling_sol_without_the_system_program_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, user, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(&user, SwapData::exact_in(true, AMOUNT, 1), &[]),
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
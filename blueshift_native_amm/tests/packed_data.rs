@@ -0,0 +1,106 @@
+//! Instruction data arrives at whatever offset the runtime places it, so the
+//! packed structs must parse from any alignment and only ever be read by
+//! value. These run natively, and under `cargo miri test` also catch any
+//! unaligned reference the parsers create. No deadline is set, so the clock
+//! is never read.
+
+use blueshift_native_amm::{DepositInstructionData, SwapInstructionData, WithdrawInstructionData};
+use pinocchio::program_error::ProgramError;
+
+fn swap_data() -> Vec<u8> {
+    let mut data = vec![5, 1];
+    data.extend_from_slice(&1_000u64.to_le_bytes()); // amount
+    data.extend_from_slice(&900u64.to_le_bytes()); // min
+    data.extend_from_slice(&0i64.to_le_bytes()); // expiration
+    data.extend_from_slice(&42u64.to_le_bytes()); // oracle_price
+    data.extend_from_slice(&150u16.to_le_bytes()); // max_divergence_bps
+    data.extend_from_slice(&7u16.to_le_bytes()); // referral_bps
+    data.push(0); // is_exact_in
+    data.extend_from_slice(&0u64.to_le_bytes()); // expiration_slot
+    data
+}
+
+fn deposit_data() -> Vec<u8> {
+    let mut data = vec![2];
+    for field in [100u64, 200, 300, 0, 50] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data
+}
+
+fn withdraw_data() -> Vec<u8> {
+    let mut data = vec![2];
+    for field in [100u64, 1, 2, 0] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.push(1);
+    data
+}
+
+/// `data` copied to each offset within an 8-byte word, so every field lands
+/// at every alignment at least once.
+fn at_every_offset(data: &[u8]) -> impl Iterator<Item = (Vec<u64>, usize)> + '_ {
+    (0..8).map(move |offset| {
+        let mut words = vec![0u64; (offset + data.len()).div_ceil(8)];
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, words.len() * 8)
+        };
+        bytes[offset..offset + data.len()].copy_from_slice(data);
+        (words, offset)
+    })
+}
+
+fn slice(words: &[u64], offset: usize, len: usize) -> &[u8] {
+    let bytes =
+        unsafe { core::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * 8) };
+    &bytes[offset..offset + len]
+}
+
+#[test]
+fn swap_data_parses_at_any_alignment() {
+    let data = swap_data();
+    for (words, offset) in at_every_offset(&data) {
+        let swap = SwapInstructionData::try_from(slice(&words, offset, data.len())).unwrap();
+        let (amount, min, expiration) = (swap.amount, swap.min, swap.expiration);
+        let (oracle_price, max_divergence_bps, referral_bps) = (
+            swap.oracle_price,
+            swap.max_divergence_bps,
+            swap.referral_bps,
+        );
+        assert_eq!((swap.is_x, amount, min, expiration), (1, 1_000, 900, 0));
+        assert_eq!(
+            (oracle_price, max_divergence_bps, referral_bps),
+            (42, 150, 7)
+        );
+        assert_eq!(swap.is_exact_in, 0);
+    }
+}
+
+#[test]
+fn deposit_and_withdraw_data_parse_at_any_alignment() {
+    let data = deposit_data();
+    for (words, offset) in at_every_offset(&data) {
+        let deposit = DepositInstructionData::try_from(slice(&words, offset, data.len())).unwrap();
+        let (amount, max_y, min_lp_out) = (deposit.amount, deposit.max_y, deposit.min_lp_out);
+        assert_eq!((amount, max_y, min_lp_out), (100, 300, 50));
+    }
+
+    let data = withdraw_data();
+    for (words, offset) in at_every_offset(&data) {
+        let withdraw =
+            WithdrawInstructionData::try_from(slice(&words, offset, data.len())).unwrap();
+        let (amount, min_y) = (withdraw.amount, withdraw.min_y);
+        assert_eq!((amount, min_y, withdraw.allow_zero_slippage), (100, 2, 1));
+    }
+}
+
+#[test]
+fn misaligned_data_of_the_wrong_length_is_rejected() {
+    let data = swap_data();
+    for (words, offset) in at_every_offset(&data) {
+        assert!(matches!(
+            SwapInstructionData::try_from(slice(&words, offset, data.len() - 1)),
+            Err(ProgramError::InvalidInstructionData)
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, program_error::ProgramError, pubkey::Pubkey};
+
+/// A shallow pool, where a tenth of the reserve moves the price by ~17%.
+const RESERVE: u64 = 10_000;
+const CAP_BPS: u16 = 500;
+
+/// A shallow pool capping price impact at `cap_bps`, and a trader holding
+/// `RESERVE` X.
+fn setup(cap_bps: u16) -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_max_price_impact_bps(cap_bps).unwrap();
+    });
+    accounts.extend(trader.accounts(&pool, RESERVE, 0, 0));
+    (pool, trader, accounts)
+}
+
+#[test]
+fn swap_within_the_price_impact_cap_succeeds() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(CAP_BPS);
+
+    // 1% of the reserve moves the price by ~2%
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, RESERVE / 100, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(amount_at(&result, &pool.vault_x), RESERVE + RESERVE / 100);
+    assert!(amount_at(&result, &trader.y_ata) > 0);
+}
+
+#[test]
+fn swap_past_the_price_impact_cap_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(CAP_BPS);
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, RESERVE / 10, 1), &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::SlippageExceeded as u32,
+        ))],
+    );
+}
+
+#[test]
+fn a_zero_cap_leaves_price_impact_unchecked() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup(0);
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, RESERVE / 10, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+}
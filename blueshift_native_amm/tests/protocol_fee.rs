@@ -0,0 +1,138 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// At 30 bps, 3_000_000 X into 997_000/1_000_000 pays a 9_000 fee and buys
+/// exactly 750_000 Y.
+const RESERVE_X: u64 = 997_000;
+const RESERVE_Y: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 3_000_000;
+const AMOUNT_OUT: u64 = 750_000;
+const FEE: u64 = 9_000;
+/// Half of every swap fee goes to the protocol.
+const PROTOCOL_FEE_BPS: u16 = 5_000;
+
+fn update_protocol_fee(pool: &Pool, authority: &Pubkey, protocol_fee: u16) -> Instruction {
+    let mut data = vec![22, 1];
+    data.extend_from_slice(&protocol_fee.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// The fee authority's X vault, where the protocol's cut of an X-in swap goes.
+fn fee_vault(pool: &Pool) -> Pubkey {
+    let (fee_authority, _) =
+        Pubkey::find_program_address(&[b"fees", pool.config.as_ref()], &PROGRAM_ID);
+    associated_token_address(&fee_authority, &pool.mint_x)
+}
+
+/// A pool administered by the returned authority, charging
+/// `protocol_fee` bps of each swap fee, and a trader holding `AMOUNT_IN` X.
+fn setup(protocol_fee: u16) -> (Pool, Pubkey, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE_Y, |config| {
+        config.set_authority(authority.to_bytes());
+        config.set_protocol_fee(protocol_fee).unwrap();
+    });
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, RESERVE_X),
+    );
+    let (fee_authority, _) =
+        Pubkey::find_program_address(&[b"fees", pool.config.as_ref()], &PROGRAM_ID);
+    accounts.push((
+        fee_vault(&pool),
+        token_account(&pool.mint_x, &fee_authority, 0),
+    ));
+    accounts.push((authority, system_account(1_000_000_000)));
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+    (pool, authority, trader, accounts)
+}
+
+#[test]
+fn update_protocol_fee_sets_the_share() {
+    let mollusk = mollusk();
+    let (pool, authority, _, accounts) = setup(0);
+
+    let result = mollusk.process_and_validate_instruction(
+        &update_protocol_fee(&pool, &authority, PROTOCOL_FEE_BPS),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).protocol_fee(),
+        PROTOCOL_FEE_BPS
+    );
+}
+
+#[test]
+fn update_protocol_fee_rejects_more_than_the_whole_fee() {
+    let mollusk = mollusk();
+    let (pool, authority, _, accounts) = setup(0);
+
+    mollusk.process_and_validate_instruction(
+        &update_protocol_fee(&pool, &authority, 10_001),
+        &accounts,
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn swap_skims_the_protocol_share_of_the_fee_out_of_the_reserves() {
+    let mollusk = mollusk();
+    let (pool, _, trader, accounts) = setup(PROTOCOL_FEE_BPS);
+    let fee_vault = fee_vault(&pool);
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_in(true, AMOUNT_IN, AMOUNT_OUT),
+            &[AccountMeta::new(fee_vault, false)],
+        ),
+        &accounts,
+        &[Check::success()],
+    );
+
+    // The trader's price is unchanged; only the LPs' half of the fee stays
+    // in the reserves
+    let protocol_cut = FEE * PROTOCOL_FEE_BPS as u64 / 10_000;
+    assert_eq!(amount_at(&result, &trader.y_ata), AMOUNT_OUT);
+    assert_eq!(amount_at(&result, &fee_vault), protocol_cut);
+    assert_eq!(
+        amount_at(&result, &pool.vault_x),
+        RESERVE_X + AMOUNT_IN - protocol_cut
+    );
+    assert_eq!(amount_at(&result, &pool.vault_y), RESERVE_Y - AMOUNT_OUT);
+}
+
+#[test]
+fn swap_with_a_protocol_fee_needs_the_fee_vault() {
+    let mollusk = mollusk();
+    let (pool, _, trader, accounts) = setup(PROTOCOL_FEE_BPS);
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(
+            &trader,
+            SwapData::exact_in(true, AMOUNT_IN, AMOUNT_OUT),
+            &[],
+        ),
+        &accounts,
+        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+    );
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::{result::Check, Mollusk};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const BALANCE: u64 = 100_000;
+const AMOUNT_IN: u64 = 10_000;
+const MAX_REFERRAL_BPS: u16 = 5_000;
+
+struct Trader {
+    user: Pubkey,
+    user_x_ata: Pubkey,
+    user_y_ata: Pubkey,
+    referrer_ata: Pubkey,
+}
+
+fn setup() -> (Pool, Trader, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = Pubkey::new_unique();
+    let referrer = Pubkey::new_unique();
+    let trader = Trader {
+        user,
+        user_x_ata: associated_token_address(&user, &pool.mint_x),
+        user_y_ata: associated_token_address(&user, &pool.mint_y),
+        referrer_ata: associated_token_address(&referrer, &pool.mint_y),
+    };
+
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_max_referral_bps(MAX_REFERRAL_BPS).unwrap();
+    });
+    accounts.push((user, system_account(1_000_000_000)));
+    accounts.push((
+        trader.user_x_ata,
+        token_account(&pool.mint_x, &user, BALANCE),
+    ));
+    accounts.push((trader.user_y_ata, token_account(&pool.mint_y, &user, 0)));
+    accounts.push((
+        trader.referrer_ata,
+        token_account(&pool.mint_y, &referrer, 0),
+    ));
+    (pool, trader, accounts)
+}
+
+/// An exact-input swap of `AMOUNT_IN` X for Y, paying `referral_bps` of the
+/// fee to the referrer's Y account when non-zero.
+fn swap_x_for_y(pool: &Pool, trader: &Trader, referral_bps: u16) -> Instruction {
+    let mut data = vec![3, 5, 1];
+    data.extend_from_slice(&AMOUNT_IN.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    data.extend_from_slice(&referral_bps.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new(trader.user, true),
+        AccountMeta::new_readonly(pool.mint_lp, false),
+        AccountMeta::new(trader.user_x_ata, false),
+        AccountMeta::new(trader.user_y_ata, false),
+        AccountMeta::new(pool.vault_x, false),
+        AccountMeta::new(pool.vault_y, false),
+        AccountMeta::new(pool.config, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    if referral_bps != 0 {
+        metas.push(AccountMeta::new(trader.referrer_ata, false));
+    }
+    Instruction::new_with_bytes(PROGRAM_ID, &data, metas)
+}
+
+fn amount_of(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+    key: &Pubkey,
+) -> u64 {
+    let result =
+        mollusk.process_and_validate_instruction(instruction, accounts, &[Check::success()]);
+    token_amount(result.get_account(key).unwrap())
+}
+
+#[test]
+fn referral_is_paid_in_the_output_token_from_the_vault() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup();
+
+    // The same trade without a referrer, for the user's baseline output
+    let baseline = amount_of(
+        &mollusk,
+        &swap_x_for_y(&pool, &trader, 0),
+        &accounts,
+        &trader.user_y_ata,
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &swap_x_for_y(&pool, &trader, MAX_REFERRAL_BPS),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let user_out = token_amount(result.get_account(&trader.user_y_ata).unwrap());
+    let referral = token_amount(result.get_account(&trader.referrer_ata).unwrap());
+    let vault_y = token_amount(result.get_account(&pool.vault_y).unwrap());
+
+    // The user's cost and output don't change; the cut comes out of the pool
+    assert_eq!(user_out, baseline);
+    assert!(referral > 0);
+    assert_eq!(RESERVE - vault_y, user_out + referral);
+    assert_eq!(
+        token_amount(result.get_account(&trader.user_x_ata).unwrap()),
+        BALANCE - AMOUNT_IN
+    );
+    assert_eq!(
+        token_amount(result.get_account(&pool.vault_x).unwrap()),
+        RESERVE + AMOUNT_IN
+    );
+}
+
+#[test]
+fn referral_above_the_pool_cap_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, trader, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &swap_x_for_y(&pool, &trader, MAX_REFERRAL_BPS + 1_000),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::ReferralTooHigh as u32,
+        ))],
+    );
+}
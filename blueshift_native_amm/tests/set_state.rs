@@ -0,0 +1,90 @@
+mod common;
+
+use blueshift_native_amm::AmmState;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+
+fn set_state(pool: &Pool, authority: &Pubkey, new_state: u8) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[4, 1, new_state],
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// A pool administered by the returned authority.
+fn setup() -> (Pool, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+    (pool, authority, accounts)
+}
+
+#[test]
+fn authority_moves_the_pool_to_withdraw_only() {
+    let mollusk = mollusk();
+    let (pool, authority, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &set_state(&pool, &authority, AmmState::WithdrawOnly as u8),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).state(),
+        AmmState::WithdrawOnly as u8
+    );
+}
+
+#[test]
+fn set_state_rejects_a_signer_other_than_the_authority() {
+    let mollusk = mollusk();
+    let (pool, _, mut accounts) = setup();
+    let intruder = Pubkey::new_unique();
+    accounts.push((intruder, system_account(1_000_000_000)));
+
+    mollusk.process_and_validate_instruction(
+        &set_state(&pool, &intruder, AmmState::WithdrawOnly as u8),
+        &accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn set_state_rejects_moving_back_to_uninitialized() {
+    let mollusk = mollusk();
+    let (pool, authority, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &set_state(&pool, &authority, AmmState::Uninitialized as u8),
+        &accounts,
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn set_state_rejects_an_unknown_state() {
+    let mollusk = mollusk();
+    let (pool, authority, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &set_state(&pool, &authority, AmmState::WithdrawOnly as u8 + 1),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
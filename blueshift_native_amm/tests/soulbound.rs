@@ -0,0 +1,170 @@
+mod common;
+
+use common::*;
+use mollusk_svm::{program::keyed_account_for_system_program, result::Check};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const BALANCE: u64 = 500_000;
+const LP_AMOUNT: u64 = 100_000;
+
+struct Provider {
+    user: Pubkey,
+    user_x_ata: Pubkey,
+    user_y_ata: Pubkey,
+    user_lp_ata: Pubkey,
+}
+
+/// A pool whose LP mint is a Token-2022 NonTransferable mint, and a
+/// provider holding X and Y and an empty LP account under Token-2022.
+fn setup() -> (Pool, Provider, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = Pubkey::new_unique();
+    let provider = Provider {
+        user,
+        user_x_ata: associated_token_address(&user, &pool.mint_x),
+        user_y_ata: associated_token_address(&user, &pool.mint_y),
+        user_lp_ata: Pubkey::new_unique(),
+    };
+
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    let mint_lp = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &pool.mint_lp)
+        .unwrap();
+    mint_lp.1 = soulbound_mint(RESERVE, &pool.config);
+
+    accounts.push((user, system_account(1_000_000_000)));
+    accounts.push((
+        provider.user_x_ata,
+        token_account(&pool.mint_x, &user, BALANCE),
+    ));
+    accounts.push((
+        provider.user_y_ata,
+        token_account(&pool.mint_y, &user, BALANCE),
+    ));
+    accounts.push((
+        provider.user_lp_ata,
+        soulbound_token_account(&pool.mint_lp, &user, 0),
+    ));
+    accounts.push(keyed_account_for_system_program());
+    accounts.push(mollusk_svm_programs_token::token2022::keyed_account());
+    (pool, provider, accounts)
+}
+
+fn deposit(pool: &Pool, provider: &Provider) -> Instruction {
+    let mut data = vec![1, 2];
+    data.extend_from_slice(&LP_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&BALANCE.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(provider.user, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(provider.user_x_ata, false),
+            AccountMeta::new(provider.user_y_ata, false),
+            AccountMeta::new(provider.user_lp_ata, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+fn withdraw(pool: &Pool, provider: &Provider) -> Instruction {
+    let mut data = vec![2, 2];
+    data.extend_from_slice(&LP_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.push(0);
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(provider.user, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(provider.user_x_ata, false),
+            AccountMeta::new(provider.user_y_ata, false),
+            AccountMeta::new(provider.user_lp_ata, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn soulbound_pool_deposits_and_withdraws() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup();
+
+    let deposited = mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(deposited.get_account(&provider.user_lp_ata).unwrap()),
+        LP_AMOUNT
+    );
+    assert_eq!(
+        token_amount(deposited.get_account(&pool.vault_x).unwrap()),
+        RESERVE + LP_AMOUNT
+    );
+
+    // Withdrawing the same LP burns it all and pays out of the vaults
+    let mut accounts = accounts;
+    for (key, account) in deposited.resulting_accounts {
+        if let Some(entry) = accounts.iter_mut().find(|(k, _)| k == &key) {
+            entry.1 = account;
+        }
+    }
+    let withdrawn = mollusk.process_and_validate_instruction(
+        &withdraw(&pool, &provider),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(withdrawn.get_account(&provider.user_lp_ata).unwrap()),
+        0
+    );
+    let user_x = token_amount(withdrawn.get_account(&provider.user_x_ata).unwrap());
+    let vault_x = token_amount(withdrawn.get_account(&pool.vault_x).unwrap());
+    assert!(user_x > BALANCE - LP_AMOUNT);
+    assert_eq!(user_x + vault_x, BALANCE + RESERVE);
+}
+
+#[test]
+fn lp_mint_outside_both_token_programs_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, provider, mut accounts) = setup();
+
+    let mint_lp = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &pool.mint_lp)
+        .unwrap();
+    mint_lp.1.owner = Pubkey::new_unique();
+
+    mollusk.process_and_validate_instruction(
+        &deposit(&pool, &provider),
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountOwner)],
+    );
+}
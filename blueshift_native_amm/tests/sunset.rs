@@ -0,0 +1,134 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState};
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const START: i64 = 1_700_000_000;
+const GRACE: i64 = 3_600;
+
+fn begin_sunset(pool: &Pool, authority: &Pubkey) -> Instruction {
+    let mut data = vec![30, 1];
+    data.extend_from_slice(&GRACE.to_le_bytes());
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+fn finalize_sunset(pool: &Pool, authority: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[31],
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// A live pool administered by the returned authority.
+fn setup() -> (Pool, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+    (pool, authority, accounts)
+}
+
+/// The pool as `BeginSunset` leaves it at `START`.
+fn sunset() -> (Pool, Pubkey, Vec<(Pubkey, Account)>) {
+    let (pool, authority, mut accounts) = setup();
+    let result = mollusk_at(START).process_and_validate_instruction(
+        &begin_sunset(&pool, &authority),
+        &accounts,
+        &[Check::success()],
+    );
+    set_account(
+        &mut accounts,
+        &pool.config,
+        result.get_account(&pool.config).unwrap().clone(),
+    );
+    (pool, authority, accounts)
+}
+
+#[test]
+fn begin_sunset_keeps_withdraws_open_for_the_grace_window() {
+    let (pool, _, accounts) = sunset();
+    let config = config_of(
+        &accounts
+            .iter()
+            .find(|(key, _)| key == &pool.config)
+            .unwrap()
+            .1,
+    );
+
+    assert_eq!(config.state(), AmmState::Disabled as u8);
+    assert_eq!(config.disabled_at(), START);
+    assert_eq!(config.grace_seconds(), GRACE);
+    assert_eq!(
+        config.effective_state(START + GRACE - 1),
+        AmmState::WithdrawOnly as u8
+    );
+}
+
+#[test]
+fn begin_sunset_rejects_a_signer_other_than_the_authority() {
+    let (pool, _, mut accounts) = setup();
+    let intruder = Pubkey::new_unique();
+    accounts.push((intruder, system_account(1_000_000_000)));
+
+    mollusk_at(START).process_and_validate_instruction(
+        &begin_sunset(&pool, &intruder),
+        &accounts,
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn finalize_sunset_waits_out_the_grace_window() {
+    let (pool, authority, accounts) = sunset();
+
+    mollusk_at(START + GRACE - 1).process_and_validate_instruction(
+        &finalize_sunset(&pool, &authority),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::SunsetPending as u32,
+        ))],
+    );
+
+    let result = mollusk_at(START + GRACE).process_and_validate_instruction(
+        &finalize_sunset(&pool, &authority),
+        &accounts,
+        &[Check::success()],
+    );
+    let config = config_of(result.get_account(&pool.config).unwrap());
+    assert_eq!(config.state(), AmmState::Disabled as u8);
+    assert_eq!(config.grace_seconds(), 0);
+}
+
+#[test]
+fn finalize_sunset_rejects_a_pool_never_sunset() {
+    let (pool, authority, accounts) = setup();
+
+    mollusk_at(START).process_and_validate_instruction(
+        &finalize_sunset(&pool, &authority),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::InvalidState as u32,
+        ))],
+    );
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 1_000;
+
+#[test]
+fn each_swap_counts_once() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(trader.accounts(&pool, 2 * AMOUNT_IN, 0, 0));
+    let swap = pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]);
+
+    for expected in 1..=2 {
+        let result =
+            mollusk.process_and_validate_instruction(&swap, &accounts, &[Check::success()]);
+        assert_eq!(
+            config_of(result.get_account(&pool.config).unwrap()).swap_count(),
+            expected
+        );
+        apply(&mut accounts, &result);
+    }
+}
+
+#[test]
+fn swap_count_saturates_instead_of_overflowing() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |config| config.set_swap_count(u64::MAX));
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).swap_count(),
+        u64::MAX
+    );
+}
+
+#[test]
+fn a_rejected_swap_is_not_counted() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+
+    // Asking for more than the whole input back can never fill
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, AMOUNT_IN), &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::SlippageExceeded as u32,
+        ))],
+    );
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).swap_count(),
+        0
+    );
+}
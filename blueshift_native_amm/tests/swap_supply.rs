@@ -0,0 +1,60 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, program_error::ProgramError, pubkey::Pubkey};
+
+/// At 30 bps, 3_000_000 X into 997_000/1_000_000 buys exactly 750_000 Y,
+/// whatever the LP supply.
+const RESERVE_X: u64 = 997_000;
+const RESERVE_Y: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 3_000_000;
+const AMOUNT_OUT: u64 = 750_000;
+/// Position of `mint_lp` in Swap's account list.
+const MINT_LP: usize = 1;
+
+/// A pool with `supply` LP outstanding, and a trader holding `AMOUNT_IN` X.
+fn setup(supply: u64) -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE_Y, |_| {});
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, RESERVE_X),
+    );
+    set_account(&mut accounts, &pool.mint_lp, lp_mint(supply, &pool.config));
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+    (pool, trader, accounts)
+}
+
+#[test]
+fn swap_output_is_independent_of_an_lp_supply_far_from_the_x_reserve() {
+    let mollusk = mollusk();
+
+    for supply in [1, RESERVE_X, 1_000_000 * RESERVE_X] {
+        let (pool, trader, accounts) = setup(supply);
+        let result = mollusk.process_and_validate_instruction(
+            &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+            &accounts,
+            &[Check::success()],
+        );
+        assert_eq!(amount_at(&result, &trader.y_ata), AMOUNT_OUT);
+    }
+}
+
+#[test]
+fn swap_rejects_an_lp_mint_outside_the_pool() {
+    let mollusk = mollusk();
+    let (pool, trader, mut accounts) = setup(RESERVE_X);
+    let spoofed = Pubkey::new_unique();
+    accounts.push((spoofed, lp_mint(RESERVE_X, &pool.config)));
+
+    let mut instruction = pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]);
+    instruction.accounts[MINT_LP].pubkey = spoofed;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use blueshift_native_amm::AmmError;
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::program_error::ProgramError;
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT_IN: u64 = 1_000;
+
+#[test]
+fn swap_settles_when_the_output_vault_moves_by_the_computed_amount() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+    let out = amount_at(&result, &trader.y_ata);
+    assert!(out > 0);
+    assert_eq!(amount_at(&result, &pool.vault_y), RESERVE - out);
+}
+
+#[test]
+fn swap_reverts_when_the_output_vault_moves_by_another_amount() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let mut trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.extend(trader.accounts(&pool, AMOUNT_IN, 0, 0));
+
+    // Stand in for a transfer hook that swallows the payout: paying the
+    // output vault back to itself leaves its balance where it was
+    trader.y_ata = pool.vault_y;
+
+    mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::TransferMismatch as u32,
+        ))],
+    );
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+const RESERVE_X: u64 = 1_000_000;
+const RESERVE_Y: u64 = 2_000_000;
+const AMOUNT_IN: u64 = 10_000;
+const START: i64 = 1_700_000_000;
+const ELAPSED: i64 = 60;
+
+/// A pool whose accumulators were last updated at `START`, and a trader
+/// holding enough X for two swaps.
+fn setup() -> (Pool, User, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let trader = User::new(&pool);
+    let mut accounts = pool.accounts(RESERVE_Y, |config| {
+        // An empty side only moves the timestamp, leaving the sums at zero
+        config.update_price_cumulatives(0, 0, START);
+    });
+    set_account(
+        &mut accounts,
+        &pool.vault_x,
+        token_account(&pool.mint_x, &pool.config, RESERVE_X),
+    );
+    accounts.extend(trader.accounts(&pool, 2 * AMOUNT_IN, 0, 0));
+    (pool, trader, accounts)
+}
+
+/// Y-per-X and X-per-Y prices as Q64.64.
+fn prices(reserve_x: u64, reserve_y: u64) -> (u128, u128) {
+    (
+        ((reserve_y as u128) << 64) / reserve_x as u128,
+        ((reserve_x as u128) << 64) / reserve_y as u128,
+    )
+}
+
+#[test]
+fn accumulators_grow_by_the_pre_trade_price_times_the_elapsed_time() {
+    let mut mollusk = mollusk_at(START);
+    let (pool, trader, mut accounts) = setup();
+    let swap = pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]);
+
+    // No time has passed since the last update, so nothing accrues
+    let first = mollusk.process_and_validate_instruction(&swap, &accounts, &[Check::success()]);
+    let config = config_of(first.get_account(&pool.config).unwrap());
+    let (x_before, y_before) = (config.price_x_cumulative(), config.price_y_cumulative());
+    assert_eq!((x_before, y_before), (0, 0));
+    let (price_x, price_y) = prices(
+        amount_at(&first, &pool.vault_x),
+        amount_at(&first, &pool.vault_y),
+    );
+    apply(&mut accounts, &first);
+
+    // The second swap credits the elapsed time to the price the first left
+    mollusk.sysvars.clock.unix_timestamp = START + ELAPSED;
+    let second = mollusk.process_and_validate_instruction(&swap, &accounts, &[Check::success()]);
+    let config = config_of(second.get_account(&pool.config).unwrap());
+    assert_eq!(
+        config.price_x_cumulative() - x_before,
+        price_x * ELAPSED as u128
+    );
+    assert_eq!(
+        config.price_y_cumulative() - y_before,
+        price_y * ELAPSED as u128
+    );
+    assert_eq!(config.last_update(), START + ELAPSED);
+}
+
+#[test]
+fn accumulators_ignore_a_clock_behind_the_last_update() {
+    let mollusk = mollusk_at(START - ELAPSED);
+    let (pool, trader, accounts) = setup();
+
+    let result = mollusk.process_and_validate_instruction(
+        &pool.swap(&trader, SwapData::exact_in(true, AMOUNT_IN, 1), &[]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    let config = config_of(result.get_account(&pool.config).unwrap());
+    assert_eq!(config.price_x_cumulative(), 0);
+    assert_eq!(config.last_update(), START);
+}
@@ -0,0 +1,268 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState, Config};
+use common::*;
+use mollusk_svm::{
+    program::keyed_account_for_system_program,
+    result::{Check, ProgramResult},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+const AMOUNT: u64 = 1_000;
+
+/// An instruction taking the authority and the config, as the admin
+/// instructions do.
+fn admin(pool: &Pool, authority: &Pubkey, data: &[u8]) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        data,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// Every instruction that mutates pool state, each with valid data.
+fn mutating(pool: &Pool, user: &User, other: &Pubkey) -> Vec<(&'static str, Instruction)> {
+    let user_meta = AccountMeta::new(user.key, true);
+    let config_meta = AccountMeta::new(pool.config, false);
+    let token_meta = AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false);
+    let system_meta = AccountMeta::new_readonly(solana_sdk::system_program::ID, false);
+
+    let mut risk_params = vec![21, 3];
+    risk_params.extend_from_slice(&[0xff; 8 * 4 + 2 * 4]);
+    let mut deposit_single = vec![24, 1, 1];
+    deposit_single.extend_from_slice(&AMOUNT.to_le_bytes());
+    deposit_single.extend_from_slice(&1u64.to_le_bytes());
+    deposit_single.extend_from_slice(&0i64.to_le_bytes());
+    let mut donate = vec![29, 1];
+    donate.extend_from_slice(&AMOUNT.to_le_bytes());
+    donate.extend_from_slice(&AMOUNT.to_le_bytes());
+    let mut flash_borrow = vec![27, 1, 1];
+    flash_borrow.extend_from_slice(&AMOUNT.to_le_bytes());
+    let mut update_authority = vec![19, 1];
+    update_authority.extend_from_slice(other.as_ref());
+
+    vec![
+        (
+            "deposit",
+            pool.deposit(
+                user,
+                DepositData {
+                    amount: AMOUNT,
+                    max_x: AMOUNT,
+                    max_y: AMOUNT,
+                    ..Default::default()
+                },
+                &[],
+            ),
+        ),
+        (
+            "deposit_single",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &deposit_single,
+                pool.deposit(user, DepositData::default(), &[]).accounts,
+            ),
+        ),
+        (
+            "withdraw",
+            pool.withdraw(
+                user,
+                WithdrawData {
+                    amount: AMOUNT,
+                    min_x: 1,
+                    min_y: 1,
+                    ..Default::default()
+                },
+            ),
+        ),
+        (
+            "swap",
+            pool.swap(user, SwapData::exact_in(true, AMOUNT, 1), &[]),
+        ),
+        (
+            "donate",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &donate,
+                vec![
+                    user_meta.clone(),
+                    AccountMeta::new(user.x_ata, false),
+                    AccountMeta::new(user.y_ata, false),
+                    AccountMeta::new(pool.vault_x, false),
+                    AccountMeta::new(pool.vault_y, false),
+                    config_meta.clone(),
+                    token_meta.clone(),
+                ],
+            ),
+        ),
+        (
+            "flash_borrow",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &flash_borrow,
+                vec![
+                    user_meta.clone(),
+                    AccountMeta::new(user.x_ata, false),
+                    AccountMeta::new(pool.vault_x, false),
+                    config_meta.clone(),
+                    token_meta.clone(),
+                    AccountMeta::new_readonly(*other, false),
+                ],
+            ),
+        ),
+        (
+            "open_position",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[13],
+                vec![
+                    user_meta.clone(),
+                    AccountMeta::new(*other, false),
+                    config_meta.clone(),
+                    system_meta.clone(),
+                ],
+            ),
+        ),
+        (
+            "init_allowlist",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[&[9, 1, 255][..], pool.mint_y.as_ref()].concat(),
+                vec![
+                    user_meta.clone(),
+                    config_meta.clone(),
+                    AccountMeta::new(*other, false),
+                    system_meta,
+                ],
+            ),
+        ),
+        (
+            "collect_protocol_fees",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[11, 1, 1],
+                vec![
+                    AccountMeta::new_readonly(*other, false),
+                    AccountMeta::new(pool.vault_x, false),
+                    AccountMeta::new(user.x_ata, false),
+                    config_meta.clone(),
+                    token_meta.clone(),
+                ],
+            ),
+        ),
+        (
+            "close_config",
+            Instruction::new_with_bytes(
+                PROGRAM_ID,
+                &[26],
+                vec![
+                    user_meta,
+                    config_meta,
+                    AccountMeta::new(pool.mint_lp, false),
+                    AccountMeta::new(pool.vault_x, false),
+                    AccountMeta::new(pool.vault_y, false),
+                    AccountMeta::new(*other, false),
+                    token_meta,
+                    AccountMeta::new(user.lp_ata, false),
+                    AccountMeta::new(user.x_ata, false),
+                    AccountMeta::new(user.y_ata, false),
+                ],
+            ),
+        ),
+        ("set_state", admin(pool, &user.key, &[4, 1, 2])),
+        (
+            "disable",
+            admin(pool, &user.key, &[&[5, 1][..], &[0; 8]].concat()),
+        ),
+        ("sweep_lamports", admin(pool, &user.key, &[8])),
+        ("renounce_authority", admin(pool, &user.key, &[10, 1, 1])),
+        ("update_fee", admin(pool, &user.key, &[16, 1, 30, 0])),
+        (
+            "update_authority",
+            admin(pool, &user.key, &update_authority),
+        ),
+        ("update_risk_params", admin(pool, &user.key, &risk_params)),
+        (
+            "update_protocol_fee",
+            admin(pool, &user.key, &[22, 1, 0, 0]),
+        ),
+        (
+            "begin_sunset",
+            admin(pool, &user.key, &[&[30, 1][..], &[0; 8]].concat()),
+        ),
+        ("finalize_sunset", admin(pool, &user.key, &[31])),
+    ]
+}
+
+/// A pool whose config is owned by the program and at the current layout,
+/// but was never initialized.
+fn setup() -> (Pool, User, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = User::new(&pool);
+    let other = Pubkey::new_unique();
+
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    let mut data = vec![0u8; Config::LEN];
+    data[0] = Config::VERSION;
+    set_account(
+        &mut accounts,
+        &pool.config,
+        Account {
+            lamports: rent_exempt(Config::LEN),
+            data,
+            owner: PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    accounts.extend(user.accounts(&pool, RESERVE, RESERVE, RESERVE));
+    accounts.push((other, system_account(1_000_000_000)));
+    accounts.push(keyed_account_for_system_program());
+    (pool, user, other, accounts)
+}
+
+#[test]
+fn every_mutating_instruction_rejects_an_uninitialized_config() {
+    let mollusk = mollusk();
+    let (pool, user, other, accounts) = setup();
+
+    for (name, instruction) in mutating(&pool, &user, &other) {
+        let result = mollusk.process_instruction(&instruction, &accounts);
+        assert_eq!(
+            result.program_result,
+            ProgramResult::Failure(ProgramError::Custom(AmmError::PoolNotInitialized as u32)),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn an_initialized_config_passes_the_guard() {
+    let mollusk = mollusk();
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    accounts.push((authority, system_account(1_000_000_000)));
+
+    let result = mollusk.process_and_validate_instruction(
+        &admin(&pool, &authority, &[4, 1, AmmState::WithdrawOnly as u8]),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        config_of(result.get_account(&pool.config).unwrap()).state(),
+        AmmState::WithdrawOnly as u8
+    );
+}
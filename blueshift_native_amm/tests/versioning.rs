@@ -0,0 +1,67 @@
+mod common;
+
+use blueshift_native_amm::{AmmError, AmmState, Config};
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+
+fn set_state(pool: &Pool, authority: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[4, 1, AmmState::WithdrawOnly as u8],
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(pool.config, false),
+        ],
+    )
+}
+
+/// A live pool administered by the returned authority, its config's
+/// version byte set to `version`.
+fn setup(version: u8) -> (Pool, Pubkey, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let authority = Pubkey::new_unique();
+    let mut accounts = pool.accounts(RESERVE, |config| {
+        config.set_authority(authority.to_bytes());
+    });
+    let config = accounts
+        .iter_mut()
+        .find(|(key, _)| key == &pool.config)
+        .unwrap();
+    config.1.data[0] = version;
+    accounts.push((authority, system_account(1_000_000_000)));
+    (pool, authority, accounts)
+}
+
+#[test]
+fn current_version_config_loads() {
+    let mollusk = mollusk();
+    let (pool, authority, accounts) = setup(Config::VERSION);
+
+    mollusk.process_and_validate_instruction(
+        &set_state(&pool, &authority),
+        &accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn v0_config_is_rejected() {
+    let mollusk = mollusk();
+    let (pool, authority, accounts) = setup(0);
+
+    mollusk.process_and_validate_instruction(
+        &set_state(&pool, &authority),
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            AmmError::VersionMismatch as u32,
+        ))],
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::*;
+use mollusk_svm::result::Check;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const RESERVE: u64 = 1_000_000;
+/// The provider's share of the pool's LP supply.
+const USER_LP: u64 = 100_000;
+
+struct Provider {
+    user: Pubkey,
+    user_x_ata: Pubkey,
+    user_y_ata: Pubkey,
+    user_lp_ata: Pubkey,
+}
+
+/// A pool where the provider holds `USER_LP` of the supply and no X or Y.
+fn setup() -> (Pool, Provider, Vec<(Pubkey, Account)>) {
+    let pool = Pool::new();
+    let user = Pubkey::new_unique();
+    let provider = Provider {
+        user,
+        user_x_ata: associated_token_address(&user, &pool.mint_x),
+        user_y_ata: associated_token_address(&user, &pool.mint_y),
+        user_lp_ata: associated_token_address(&user, &pool.mint_lp),
+    };
+
+    let mut accounts = pool.accounts(RESERVE, |_| {});
+    accounts.push((user, system_account(1_000_000_000)));
+    accounts.push((provider.user_x_ata, token_account(&pool.mint_x, &user, 0)));
+    accounts.push((provider.user_y_ata, token_account(&pool.mint_y, &user, 0)));
+    accounts.push((
+        provider.user_lp_ata,
+        token_account(&pool.mint_lp, &user, USER_LP),
+    ));
+    (pool, provider, accounts)
+}
+
+/// Burns `amount` LP with no minimums on either side.
+fn withdraw_without_minimums(
+    pool: &Pool,
+    provider: &Provider,
+    amount: u64,
+    allow_zero_slippage: bool,
+) -> Instruction {
+    let mut data = vec![2, 2];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.push(allow_zero_slippage as u8);
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(provider.user, true),
+            AccountMeta::new(pool.mint_lp, false),
+            AccountMeta::new(pool.vault_x, false),
+            AccountMeta::new(pool.vault_y, false),
+            AccountMeta::new(provider.user_x_ata, false),
+            AccountMeta::new(provider.user_y_ata, false),
+            AccountMeta::new(provider.user_lp_ata, false),
+            AccountMeta::new(pool.config, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+#[test]
+fn full_exit_needs_no_minimums() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup();
+
+    // The whole LP balance, though far short of the pool's supply
+    let result = mollusk.process_and_validate_instruction(
+        &withdraw_without_minimums(&pool, &provider, USER_LP, false),
+        &accounts,
+        &[Check::success()],
+    );
+
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        0
+    );
+    assert!(token_amount(result.get_account(&provider.user_x_ata).unwrap()) > 0);
+}
+
+#[test]
+fn partial_withdraw_without_minimums_is_rejected_unless_flagged() {
+    let mollusk = mollusk();
+    let (pool, provider, accounts) = setup();
+
+    mollusk.process_and_validate_instruction(
+        &withdraw_without_minimums(&pool, &provider, USER_LP / 2, false),
+        &accounts,
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &withdraw_without_minimums(&pool, &provider, USER_LP / 2, true),
+        &accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        token_amount(result.get_account(&provider.user_lp_ata).unwrap()),
+        USER_LP / 2
+    );
+}